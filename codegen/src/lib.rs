@@ -34,14 +34,23 @@ enum State {
 
 #[derive(Clone)]
 struct ParserTransformer {
+    scope: TokenStream,
     input: syn::Expr,
+    info: syn::Ident,
+    marker: syn::Expr,
     output: syn::Type,
     state: State,
 }
 
 impl ParserTransformer {
-    fn new(input: syn::Expr, output: syn::Type) -> ParserTransformer {
-        ParserTransformer { input, output, state: State::Start }
+    fn new(
+        scope: TokenStream,
+        input: syn::Expr,
+        info: syn::Ident,
+        marker: syn::Expr,
+        output: syn::Type
+    ) -> ParserTransformer {
+        ParserTransformer { scope, input, info, marker, output, state: State::Start }
     }
 }
 
@@ -52,8 +61,19 @@ impl VisitMut for ParserTransformer {
         visit_mut::visit_expr_try_mut(self, v);
         self.state = last_state;
 
-        let expr = &v.expr;
-        let new_expr = quote_spanned!(expr.span() => #expr.map_err(|e| e.into()));
+        // Lift a `Result` with a foreign error into a parser `Result`. See
+        // `pear::result::lift` for how the two are told apart.
+        let (expr, scope) = (&v.expr, &self.scope);
+        let (input, info, marker) = (&self.input, &self.info, &self.marker);
+        let new_expr = quote_spanned!(expr.span() => ({
+            #[allow(unused_imports)]
+            use #scope::result::lift::{ParseErrorKind as _, ForeignErrorKind as _};
+            match #expr {
+                ___r => (&___r).lift_kind().lift(___r, |___e| {
+                    #scope::result::lift::foreign(#input, #info, *#marker, ___e)
+                }),
+            }
+        }).map_err(|e| e.into()));
         let method_call: syn::Expr = syn::parse2(new_expr).expect("okay");
         v.expr = Box::new(method_call);
     }
@@ -146,12 +166,53 @@ fn wrapping_fn_block(
 
     let rewind_expr = |span| quote_spanned! { span =>
         <#input_ty as #scope::input::Rewind>::rewind_to(#input, ___mark);
+        #input.restore_state(___state);
     };
 
     let (rewind, peek) = (args.rewind.map(rewind_expr), args.peek.map(rewind_expr));
+    let error_expr = |expected: TokenStream| quote_spanned!(span => {
+        let ___ctxt = #scope::input::Input::context(#input, ___mark);
+        let ___expected = #scope::input::Expected::<#input_ty>::#expected;
+        let ___error = #scope::error::ParseError::new(___info, ___expected, ___ctxt);
+        Err(#scope::error::Failure::Error(___error.into()))
+    });
+
+    let exceeded = error_expr(quote!(LimitExceeded(___limit)));
+    let call = match args.raw.is_some() {
+        true => quote_spanned!(span => #result_map(&___info, &mut ___mark)),
+        false => {
+            let too_deep = error_expr(quote!(TooDeep { limit: ___limit }));
+            quote_spanned!(span => {
+                #input.depth += 1;
+                let ___res: #ret_ty = match #input.options.max_depth {
+                    Some(___limit) if #input.depth > ___limit => {
+                        #input.fatal = true;
+                        #too_deep
+                    },
+                    _ => #scope::input::grow_stack(|| #result_map(&___info, &mut ___mark)),
+                };
+
+                #input.depth -= 1;
+                ___res
+            })
+        }
+    };
+
     let new_block_tokens = {
-        let (name, raw) = (&function.sig.ident, args.raw.is_some());
-        let name_str = name.to_string();
+        let (name, raw, commit) = (&function.sig.ident, args.raw.is_some(), args.commit.is_some());
+        let hidden = args.hidden.is_some();
+        let name_str = args.label.as_ref().map(|l| l.value()).unwrap_or_else(|| name.to_string());
+        let suggest = (!raw).then(|| quote_spanned!(span => {
+            let ___error = match ___res {
+                Err(#scope::error::Failure::Error(ref mut ___e)) => Some(___e),
+                _ => None,
+            };
+
+            if let (0, true, Some(___e)) = (#input.depth, #input.emit_error, ___error) {
+                #input.suggest(___e);
+            }
+        }));
+
         quote_spanned!(span => {
             let ___info = #scope::input::ParserInfo { name: #name_str, raw: #raw };
             if let Some(ref mut ___debugger) = #input.options.debugger {
@@ -159,17 +220,47 @@ fn wrapping_fn_block(
             }
 
             let mut ___mark = #scope::input::Input::mark(#input, &___info);
-            let mut ___res: #ret_ty = #result_map(&___info, &mut ___mark);
+            let ___state = #input.checkpoint_state();
+            let (___committed, ___emit_error) = (#input.committed, #input.emit_error);
+            #input.committed = false;
+            if #commit {
+                #input.commit();
+            }
+
+            let mut ___res: #ret_ty = match #input.check_limits() {
+                Some(___limit) => #exceeded,
+                None => #call,
+            };
+
+            if let (Ok(_), Some(___limit)) = (&___res, #input.exceeded()) {
+                ___res = #exceeded;
+            }
+
+            if #input.committed && ___res.is_err() {
+                #input.fatal = true;
+            }
+
+            #input.committed = ___committed;
+            #input.emit_error = ___emit_error;
+
             match ___res {
                 Ok(_) => { #peek },
-                Err(ref mut ___e) if #input.options.stacked_context => {
-                    let ___ctxt = #scope::input::Input::context(#input, ___mark);
-                    ___e.push_info(___info, ___ctxt);
+                Err(#scope::error::Failure::Error(ref mut ___e))
+                    if #input.options.stacked_context && !#hidden
+                    && (#input.emit_error || #input.is_fatal()) =>
+                {
+                    let ___policy = #input.options.stack_policy;
+                    ___e.push_info_with(___info, ___policy, || {
+                        #scope::input::Input::context(#input, ___mark)
+                    });
+
                     #rewind
                 },
                 Err(_) => { #rewind },
             }
 
+            #suggest
+
             if #input.options.debugger.is_some() {
                 let ___ctxt = #scope::input::Input::context(#input, ___mark);
                 if let Some(ref mut ___debugger) = #input.options.debugger {
@@ -177,6 +268,7 @@ fn wrapping_fn_block(
                 }
             }
 
+            #input.release_state(___state);
             ___res
         })
     };
@@ -201,11 +293,22 @@ fn parser_attribute(input: proc_macro::TokenStream, args: &AttrArgs) -> PResult<
 
     let (input_ident, _) = extract_input_ident_ty(&function)?;
     let input_expr: syn::Expr = syn::parse2(quote!(#input_ident)).unwrap();
-    let mut transformer = ParserTransformer::new(input_expr, ret_ty.clone());
+    let info = parser_info_ident(function.sig.ident.span());
+    let marker = parse_marker_ident(input_ident.span());
+    let marker_expr: syn::Expr = syn::parse2(quote!(#marker)).unwrap();
+    let scope = args.raw.map(|_| quote!(crate)).unwrap_or_else(|| quote!(pear));
+    let mut transformer = ParserTransformer::new(
+        scope.clone(), input_expr, info, marker_expr, ret_ty.clone()
+    );
+
     visit_mut::visit_item_fn_mut(&mut transformer, &mut function);
 
-    let scope = args.raw.map(|_| quote!(crate)).unwrap_or_else(|| quote!(pear));
-    let inline = syn::Attribute::parse_outer.parse2(quote!(#[inline])).unwrap();
+    // The closure wrapping the body is called immediately, and the size of the
+    // error is dictated by `ParseError`, not by the user's function.
+    let inline = syn::Attribute::parse_outer.parse2(quote! {
+        #[inline]
+        #[allow(clippy::redundant_closure_call, clippy::result_large_err)]
+    }).unwrap();
     function.block = Box::new(wrapping_fn_block(&function, scope, args, &ret_ty)?);
     function.attrs.extend(inline);
 
@@ -222,7 +325,9 @@ impl Case {
         };
 
         let (input, output) = (&context.input, &context.output);
-        let mut transformer = ParserTransformer::new(input.clone(), output.clone());
+        let mut transformer = ParserTransformer::new(
+            quote!(pear), input.clone(), context.info.clone(), context.marker.clone(), output.clone()
+        );
         let mut case_expr = this.expr.clone();
         visit_mut::visit_expr_mut(&mut transformer, &mut case_expr);
 
@@ -248,9 +353,14 @@ impl Case {
                     let mut call = call.expr.clone();
                     call.args.insert(0, input.clone());
                     quote!({
-                        let ___preserve_error = #input.emit_error;
-                        #input.emit_error = false;
+                        let ___preserve_error = #input.speculate();
+                        let ___state = #input.checkpoint_state();
                         let ___call_result = #call;
+                        if ___call_result.is_err() {
+                            #input.restore_state(___state);
+                        }
+
+                        #input.release_state(___state);
                         #input.emit_error = ___preserve_error;
                         ___call_result
                     })
@@ -261,7 +371,10 @@ impl Case {
 
                 quote_spanned! { this.span =>
                     #(
-                        #prefix let Some(#name) = #call_expr.ok() {
+                        #prefix let Some(#name) = match #call_expr {
+                            Err(___e) if #input.is_fatal() => return Err(___e.into()),
+                            ___call_result => ___call_result.ok(),
+                        } {
                             #case_expr
                         }
                      )* else {
@@ -287,6 +400,10 @@ impl Switch {
 ///   here `I` implements [`Input`]. This is the _input_ parameter.
 /// - The return type must be [`Result<O, I>`] where `I` is the inner type
 ///   of the input parameter and `O` can be any type.
+/// - If the return type is `Result<O, I, E>` with a custom error type `E`,
+///   `E` must implement `From<Expected<I>>`: the function fails with an
+///   [`Expected::LimitExceeded`] or [`Expected::TooDeep`] converted into `E`
+///   when a limit is exceeded, even if it never calls a built-in parser.
 ///
 /// The following transformations are applied to the _contents_ of the
 /// attributed function:
@@ -296,6 +413,11 @@ impl Switch {
 ///   `?`. That is, every function call of the form `foo(a, b, c, ...)?` is
 ///   converted to `foo(input, a, b, c, ...)?` where `input` is the input
 ///   parameter.
+/// - The operand of every postfix `?` may also be a `Result<T, F>` where `F`
+///   is any `std::error::Error + Send + Sync + 'static`, as returned by
+///   `str::parse()`. The error is wrapped in an `Expected::Foreign` with the
+///   context of the current parser and is the error's `source()`. The type
+///   of the `Result` must be known at the `?`: write `s.parse::<u8>()?`.
 /// - The inputs to every macro whose name starts with `parse_` are prefixed
 ///   with `[PARSER_NAME, INPUT, MARKER, OUTPUT]` where `PARSER_NAME` is the
 ///   raw string literal of the functon's name, `INPUT` is the input
@@ -318,12 +440,35 @@ impl Switch {
 /// function:
 ///
 /// - The [`Input::mark()`] method is called before the function executes.
-///   The returned mark, if any, is stored on the stack.
+///   The returned mark, if any, is stored on the stack. A checkpoint of the
+///   user state is taken alongside it with [`Pear::checkpoint_state()`]. When
+///   the function rewinds the input to the mark, the user state is restored
+///   to the checkpoint.
+/// - Unless the parser is `raw`, the input's nesting depth is incremented
+///   for the duration of the function. If it exceeds the `max_depth` set with
+///   [`Pear::with_max_depth()`], the function fails with
+///   [`Expected::TooDeep`] without executing; the failure is marked fatal.
+///   With the `stacker` feature of `pear`, the stack is grown onto the heap
+///   before the function executes if little of it remains.
+/// - The limits in the input's [`Options`] are checked before the function
+///   executes, spending a unit of fuel. If a limit is exceeded, before or
+///   while the function executes, the function fails with
+///   [`Expected::LimitExceeded`].
+/// - If the function fails after committing, either with `#[parser(commit)]`
+///   or by invoking [`parse_commit!()`], the failure is marked fatal: see
+///   [`Pear::is_fatal()`]. A fatal failure is propagated by `switch!`,
+///   `parse_try!` and [`try_collect()`] instead of being recovered from.
 /// - A return value of `O` is automatically converted (or "lifted") into a
 ///   type of [`Result<O, I>`] by wrapping it in `Ok`.
 /// - If the function returns an `Err`, [`Input::context()`] is called with
 ///   the current mark, and the returned context, if any, is pushed into the
-///   error via [`ParseError::push_context()`].
+///   error via [`ParseError::push_context()`]. The [`StackPolicy`] in the
+///   input's [`Options`] may prune the context instead. Nor is it pushed for
+///   a failure that won't be reported: see [`Result<O, I>`].
+///   With `#[parser(hidden)]`, the context is never pushed. With
+///   `#[parser(label = "name")]`, the parser is named `name` instead of the
+///   function's name, in the stack and in the [`ParserInfo`] passed to the
+///   input and debugger.
 /// - The [`Input::unmark()`] method is called after the function executes,
 ///   passing in the current mark.
 ///
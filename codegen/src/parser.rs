@@ -223,26 +223,69 @@ pub struct AttrArgs {
     pub raw: Option<Span>,
     pub rewind: Option<Span>,
     pub peek: Option<Span>,
+    pub commit: Option<Span>,
+    pub hidden: Option<Span>,
+    pub label: Option<syn::LitStr>,
+}
+
+/// An attribute argument: `name` or `name = "value"`.
+struct AttrArg {
+    name: syn::Ident,
+    value: Option<syn::LitStr>,
+}
+
+impl syn::parse::Parse for AttrArg {
+    fn parse(input: SynParseStream) -> syn::parse::Result<Self> {
+        let name = input.parse()?;
+        let value = match input.peek(Token![=]) {
+            true => { input.parse::<Token![=]>()?; Some(input.parse()?) }
+            false => None,
+        };
+
+        Ok(AttrArg { name, value })
+    }
 }
 
 impl Parse for AttrArgs {
     fn parse(input: SynParseStream) -> PResult<Self> {
-        let args = input.call(<Punctuated<syn::Ident, Token![,]>>::parse_terminated)?;
-        let (mut raw, mut rewind, mut peek) = Default::default();
-        for case in args.iter() {
+        let args = input.call(<Punctuated<AttrArg, Token![,]>>::parse_terminated)?;
+        let (mut raw, mut rewind, mut peek, mut commit, mut hidden) = Default::default();
+        let mut label = None;
+        for AttrArg { name: case, value } in args.into_iter() {
+            match (value, case == "label") {
+                (Some(value), true) => {
+                    label = Some(value);
+                    continue;
+                }
+                (None, true) => {
+                    return Err(case.span().error("`label` requires a value")
+                        .help("write `label = \"name\"`"));
+                }
+                (Some(value), false) => {
+                    return Err(value.span()
+                        .error(format!("attribute argument `{}` does not take a value", case)));
+                }
+                (None, false) => (),
+            }
+
             if case == "raw" {
                 raw = Some(case.span());
             } else if case == "rewind" {
                 rewind = Some(case.span());
             } else if case == "peek" {
                 peek = Some(case.span());
+            } else if case == "commit" {
+                commit = Some(case.span());
+            } else if case == "hidden" {
+                hidden = Some(case.span());
             } else {
                 return Err(case.span()
                            .error(format!("unknown attribute argument `{}`", case))
-                           .help("supported arguments are: `rewind`, `peek`"));
+                           .help("supported arguments are: `rewind`, `peek`, `commit`, \
+                                 `hidden`, `label = \"name\"`"));
             }
         }
 
-        Ok(AttrArgs { raw, rewind, peek })
+        Ok(AttrArgs { raw, rewind, peek, commit, hidden, label })
     }
 }
@@ -1,11 +1,11 @@
-use pear::input::Result;
+use pear::input::{Pear, ParseError, Text};
 use pear::macros::parse;
 
 use json::*;
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
 
 #[inline(always)]
-fn parse_json<'a, I: Input<'a>>(input: I) -> Result<JsonValue<'a>, I> {
+fn parse_json<'a, I: Input<'a>>(input: I) -> Result<JsonValue<'a>, ParseError<I>> {
     let result = parse!(value: input);
     assert!(result.is_ok());
     result
@@ -22,10 +22,42 @@ fn parse_json<'a, I: Input<'a>>(input: I) -> Result<JsonValue<'a>, I> {
 // if it parses. As a result, our parser will be much slower. You can immitate
 // the PEST parser's behavior by changing the parser so that it doesn't build
 // real values and instead returns dummy values.
+#[allow(clippy::result_large_err)]
 pub fn simple_data(c: &mut Criterion) {
     let data = include_str!("../assets/simple.json");
     c.bench_function("simple", |b| b.iter(|| black_box(parse_json(data))));
 }
 
-criterion_group!(json, simple_data);
+// A document of many small values. Each value is found by trying the
+// alternatives in `value`'s `switch!` in turn, so most parsers that run fail
+// speculatively, and their errors are never reported.
+fn many_values(count: usize) -> String {
+    let item = r#"{"a": [null, true, false, -1.5e3], "b": "c"}"#;
+    format!("[{}]", vec![item; count].join(", "))
+}
+
+// Parses `data`, building speculative failures in full unless `elide`.
+fn parse_text(data: &str, elide: bool) -> bool {
+    let mut input = Pear::<Text<'_>>::new(data);
+    input.options.elide_failures = elide;
+    parse!(value: &mut input).is_ok()
+}
+
+// Compares eliding the details of speculative failures, the default, with
+// building them in full, for a document that parses and one that doesn't.
+pub fn speculative(c: &mut Criterion) {
+    let valid = many_values(1_000);
+    let invalid = many_values(1_000) + "x";
+    let mut group = c.benchmark_group("speculative");
+    for &(name, elide) in &[("elided", true), ("detailed", false)] {
+        group.bench_function(name, |b| b.iter(|| assert!(parse_text(black_box(&valid), elide))));
+        group.bench_function(format!("{}_error", name), |b| {
+            b.iter(|| assert!(!parse_text(black_box(&invalid), elide)))
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(json, simple_data, speculative);
 criterion_main!(json);
@@ -27,11 +27,16 @@ fn is_num(c: &char) -> bool {
     c.is_ascii_digit()
 }
 
+/// The maximum nesting of parsers when parsing a document. Each level of
+/// arrays or objects nests two parsers; without a limit, input nested deeply
+/// enough overflows the stack.
+pub const MAX_DEPTH: usize = 64;
+
 parse_declare!(pub Input<'a>(Token = char, Slice = &'a str, Many = &'a str));
 
 #[parser]
 fn int<'a, I: Input<'a>>(input: &mut Pear<I>) -> Result<i64, I> {
-    take_some_while(is_num)?.parse().or_else(|e| parse_error!("{}", e)?)
+    take_some_while(is_num)?.parse::<i64>()?
     // take_some_while(|c| ('0'..='9').contains(c)); // BENCH
     // 1 // BENCH
 }
@@ -50,8 +55,7 @@ fn number<'a, I: Input<'a>>(input: &mut Pear<I>) -> Result<f64, I> {
     let exp = switch! { eat_if(|&c| "eE".contains(c)) => signed_int()?, _ => 0 };
 
     // NOT BENCH
-    format!("{}.{}e{}", whole_num, frac, exp).parse()
-        .or_else(|e| parse_error!("{}", e)?)
+    format!("{}.{}e{}", whole_num, frac, exp).parse::<f64>()?
 
     // 0.0 // BENCH
 }
@@ -1,3 +1,4 @@
+use pear::input::{Pear, Text};
 use pear::macros::parse;
 
 use json::*;
@@ -20,7 +21,8 @@ fn main() {
         "escaped characters": "\u2192\uD83D\uDE00\"\t\uD834\uDD1E"
     }"#;
 
-    let result = parse!(value: pear::input::Text::from(test));
+    let mut input = Pear::<Text<'_>>::new(test).with_max_depth(MAX_DEPTH).with_suggestions();
+    let result = parse!(value: &mut input);
     match result {
         Ok(v) => println!("Value: {:#?}", v),
         Err(e) => println!("Error: {}", e)
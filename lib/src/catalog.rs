@@ -0,0 +1,341 @@
+//! Localizable messages for parse errors.
+//!
+//! The messages of [`Expected`](crate::error::Expected), [`Limit`], and the
+//! stack, label, note, and help lines of a [`ParseError`] are looked up by id
+//! in a [`Catalog`]. Their `Display` implementations use the [`English`]
+//! catalog; [`Localize::localized()`] renders them with any other:
+//!
+//! ```rust
+//! use pear::catalog::{Messages, Localize};
+//! use pear::input::{Pear, Text};
+//! use pear::parsers::eat;
+//!
+//! let spanish = Messages::parse(r#"
+//! ## Mensajes en español.
+//! expected-token-found = se esperaba { $expected } pero se encontró { $found }
+//! stack = en { $parser } { $context }
+//! "#).unwrap();
+//!
+//! let error = eat(&mut Pear::<Text<'_>>::new("b"), 'a').unwrap_err().unwrap();
+//! let message = error.error.localized(&spanish).to_string();
+//! assert_eq!(message, "se esperaba 'a' pero se encontró 'b'");
+//! ```
+//!
+//! A [`Messages`] catalog is loaded from a subset of the
+//! [Fluent](https://projectfluent.org/) syntax: each message is an
+//! `id = pattern` line, optionally continued on indented lines, and lines
+//! starting with `#` are comments. Patterns refer to arguments as
+//! `{ $name }` and may include literal text as `{ "text" }`. Messages
+//! missing from a catalog fall back to English. [`ENGLISH`] lists every
+//! message id, its arguments, and its English text, and may be used as the
+//! template for a translation.
+//!
+//! Errors of custom types can be localized by implementing [`Localize`].
+
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::OnceLock;
+
+use crate::error::{ParseError, Expected, Failure};
+use crate::input::{Show, Limit};
+
+/// The English messages in catalog syntax: the messages of the [`English`]
+/// catalog, and the fallback for messages missing from a [`Messages`].
+pub const ENGLISH: &str = r#"
+expected-token-found = expected token { $expected } but found { $found }
+unexpected-token = unexpected token: { $found }
+expected-token-none = expected token { $expected } but none was found
+expected-any-token = expected any token but none was found
+expected-slice-found = expected slice { $expected } but found { $found }
+unexpected-slice = unexpected slice: { $found }
+expected-slice-none = expected slice { $expected } but none was found
+expected-any-slice = expected any slice but none was found
+expected-eof = expected EOF but input remains
+expected-eof-found = expected EOF but found { $found }
+incomplete-needed = expected { $needed } more token(s) but input is incomplete
+incomplete = expected more input but input is incomplete
+malformed = invalid { $encoding } at byte offset { $offset }
+too-deep = nesting too deep: more than { $limit } levels
+error-elided = [ERROR ELIDED]
+limit-fuel = parsing budget exhausted
+limit-input-length = input is longer than the maximum length of { $max }
+limit-cancelled = parsing was cancelled
+stack = { $parser } { $context }
+stack-repeated = { $parser } ×{ $count } { $context }
+label = { $message } { $context }
+note = note: { $note }
+help = help: { $help }
+"#;
+
+/// The named arguments of a message.
+pub type Args<'a> = [(&'a str, &'a dyn fmt::Display)];
+
+/// A catalog of messages, looked up by id.
+pub trait Catalog {
+    /// Writes the message `id`, with the arguments `args`, to `f`.
+    fn message(&self, f: &mut fmt::Formatter<'_>, id: &str, args: &Args<'_>) -> fmt::Result;
+}
+
+/// The catalog of [`ENGLISH`] messages, used by the `Display` implementations
+/// of errors.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct English;
+
+impl Catalog for English {
+    fn message(&self, f: &mut fmt::Formatter<'_>, id: &str, args: &Args<'_>) -> fmt::Result {
+        static MESSAGES: OnceLock<HashMap<&str, &str>> = OnceLock::new();
+        let messages = MESSAGES.get_or_init(|| {
+            ENGLISH.lines().filter_map(|line| line.split_once(" = ")).collect()
+        });
+
+        match messages.get(id) {
+            Some(pattern) => render(f, pattern, args),
+            None => f.write_str(id),
+        }
+    }
+}
+
+/// A catalog of messages loaded from catalog syntax, falling back to
+/// [`English`] for messages it doesn't contain.
+#[derive(Debug, Clone, Default)]
+pub struct Messages {
+    messages: HashMap<String, String>,
+}
+
+/// An error in the syntax of a catalog.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CatalogError {
+    /// The line of the error, starting at 1.
+    pub line: usize,
+    /// What's wrong with the line.
+    pub reason: &'static str,
+}
+
+impl fmt::Display for CatalogError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid catalog at line {}: {}", self.line, self.reason)
+    }
+}
+
+impl std::error::Error for CatalogError {  }
+
+impl Messages {
+    /// Parses a catalog from `source`. See the [module
+    /// documentation](crate::catalog) for the syntax.
+    pub fn parse(source: &str) -> Result<Messages, CatalogError> {
+        let mut messages: HashMap<String, String> = HashMap::new();
+        let mut last: Option<String> = None;
+        for (i, line) in source.lines().enumerate() {
+            let error = |reason| CatalogError { line: i + 1, reason };
+            if line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            if line.starts_with(char::is_whitespace) {
+                let pattern = last.as_ref()
+                    .and_then(|id| messages.get_mut(id))
+                    .ok_or_else(|| error("continuation line without a message"))?;
+
+                if !pattern.is_empty() {
+                    pattern.push('\n');
+                }
+
+                pattern.push_str(line.trim());
+                validate(pattern).map_err(error)?;
+                continue;
+            }
+
+            let (id, pattern) = line.split_once('=').ok_or_else(|| error("expected `id = pattern`"))?;
+            let (id, pattern) = (id.trim(), pattern.trim());
+            let valid_id = id.starts_with(|c: char| c.is_ascii_alphabetic())
+                && id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+
+            if !valid_id {
+                return Err(error("invalid message id"));
+            }
+
+            validate(pattern).map_err(error)?;
+            messages.insert(id.into(), pattern.into());
+            last = Some(id.into());
+        }
+
+        Ok(Messages { messages })
+    }
+
+    /// Returns the pattern of the message `id`, if this catalog contains it.
+    pub fn get(&self, id: &str) -> Option<&str> {
+        self.messages.get(id).map(|s| s.as_str())
+    }
+}
+
+impl Catalog for Messages {
+    fn message(&self, f: &mut fmt::Formatter<'_>, id: &str, args: &Args<'_>) -> fmt::Result {
+        match self.get(id) {
+            Some(pattern) => render(f, pattern, args),
+            None => English.message(f, id, args),
+        }
+    }
+}
+
+/// A `{ $argument }` or `{ "text" }` placeable in a pattern.
+enum Placeable<'a> {
+    Argument(&'a str),
+    Text(&'a str),
+}
+
+/// Finds the first placeable in `pattern`, returning the text before it, the
+/// placeable, and the text after it.
+fn next_placeable(pattern: &str) -> Result<Option<(&str, Placeable<'_>, &str)>, &'static str> {
+    let start = match pattern.find('{') {
+        Some(start) => start,
+        None => return Ok(None),
+    };
+
+    let (before, inner) = (&pattern[..start], pattern[start + 1..].trim_start());
+    let (placeable, rest) = if let Some(text) = inner.strip_prefix('"') {
+        let end = text.find('"').ok_or("unclosed string literal")?;
+        (Placeable::Text(&text[..end]), text[end + 1..].trim_start())
+    } else if let Some(name) = inner.strip_prefix('$') {
+        let end = name.find(|c: char| !(c.is_alphanumeric() || c == '-' || c == '_'));
+        let end = end.unwrap_or(name.len());
+        (Placeable::Argument(&name[..end]), name[end..].trim_start())
+    } else {
+        return Err("expected `{ $argument }` or `{ \"text\" }`");
+    };
+
+    match rest.strip_prefix('}') {
+        Some(after) => Ok(Some((before, placeable, after))),
+        None => Err("unclosed `{`"),
+    }
+}
+
+/// Checks that every placeable in `pattern` is well-formed.
+fn validate(mut pattern: &str) -> Result<(), &'static str> {
+    while let Some((_, _, rest)) = next_placeable(pattern)? {
+        pattern = rest;
+    }
+
+    Ok(())
+}
+
+/// Writes `pattern` to `f`, replacing each placeable with its argument or
+/// text. An argument that's missing from `args` is written as is.
+fn render(f: &mut fmt::Formatter<'_>, mut pattern: &str, args: &Args<'_>) -> fmt::Result {
+    while let Ok(Some((before, placeable, rest))) = next_placeable(pattern) {
+        f.write_str(before)?;
+        match placeable {
+            Placeable::Text(text) => f.write_str(text)?,
+            Placeable::Argument(name) => match args.iter().find(|(arg, _)| *arg == name) {
+                Some((_, value)) => write!(f, "{}", value)?,
+                None => write!(f, "{{ ${} }}", name)?,
+            },
+        }
+
+        pattern = rest;
+    }
+
+    f.write_str(pattern)
+}
+
+/// A value whose message can be rendered with any [`Catalog`].
+pub trait Localize {
+    /// Writes the message of `self`, as found in `catalog`, to `f`.
+    fn localize(&self, f: &mut fmt::Formatter<'_>, catalog: &dyn Catalog) -> fmt::Result;
+
+    /// Returns a value that displays the message of `self` as found in
+    /// `catalog`.
+    fn localized<'a>(&'a self, catalog: &'a dyn Catalog) -> Localized<'a, Self> {
+        Localized { value: self, catalog }
+    }
+}
+
+/// Displays a value with the messages of a catalog. Returned by
+/// [`Localize::localized()`].
+pub struct Localized<'a, T: ?Sized> {
+    value: &'a T,
+    catalog: &'a dyn Catalog,
+}
+
+impl<T: Localize + ?Sized> fmt::Display for Localized<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.value.localize(f, self.catalog)
+    }
+}
+
+impl Localize for Limit {
+    fn localize(&self, f: &mut fmt::Formatter<'_>, catalog: &dyn Catalog) -> fmt::Result {
+        match *self {
+            Limit::Fuel => catalog.message(f, "limit-fuel", &[]),
+            Limit::InputLength(max) => catalog.message(f, "limit-input-length", &[("max", &max)]),
+            Limit::Cancelled => catalog.message(f, "limit-cancelled", &[]),
+        }
+    }
+}
+
+impl<T: Show, S: Show> Localize for Expected<T, S> {
+    fn localize(&self, f: &mut fmt::Formatter<'_>, catalog: &dyn Catalog) -> fmt::Result {
+        match *self {
+            Expected::Token(Some(ref expected), Some(ref found)) => {
+                let found = found as &dyn Show;
+                catalog.message(f, "expected-token-found", &[("expected", expected), ("found", &found)])
+            }
+            Expected::Token(None, Some(ref found)) => {
+                let found = found as &dyn Show;
+                catalog.message(f, "unexpected-token", &[("found", &found)])
+            }
+            Expected::Token(Some(ref expected), None) => {
+                catalog.message(f, "expected-token-none", &[("expected", expected)])
+            }
+            Expected::Token(None, None) => catalog.message(f, "expected-any-token", &[]),
+            Expected::Slice(Some(ref expected), Some(ref found)) => {
+                let found = found as &dyn Show;
+                catalog.message(f, "expected-slice-found", &[("expected", expected), ("found", &found)])
+            }
+            Expected::Slice(None, Some(ref found)) => {
+                let found = found as &dyn Show;
+                catalog.message(f, "unexpected-slice", &[("found", &found)])
+            }
+            Expected::Slice(Some(ref expected), None) => {
+                catalog.message(f, "expected-slice-none", &[("expected", expected)])
+            }
+            Expected::Slice(None, None) => catalog.message(f, "expected-any-slice", &[]),
+            Expected::Eof(None) => catalog.message(f, "expected-eof", &[]),
+            Expected::Eof(Some(ref found)) => {
+                let found = found as &dyn Show;
+                catalog.message(f, "expected-eof-found", &[("found", &found)])
+            }
+            Expected::Incomplete { needed: Some(needed) } => {
+                catalog.message(f, "incomplete-needed", &[("needed", &needed)])
+            }
+            Expected::Incomplete { needed: None } => catalog.message(f, "incomplete", &[]),
+            Expected::Malformed { encoding, offset } => {
+                catalog.message(f, "malformed", &[("encoding", &encoding), ("offset", &offset)])
+            }
+            Expected::TooDeep { limit } => catalog.message(f, "too-deep", &[("limit", &limit)]),
+            Expected::LimitExceeded(limit) => limit.localize(f, catalog),
+            Expected::Foreign(ref e) => write!(f, "{}", e),
+            Expected::Other(ref other) => write!(f, "{}", other),
+            Expected::Elided => catalog.message(f, "error-elided", &[]),
+        }
+    }
+}
+
+impl<C: Show, E: Localize> Localize for ParseError<C, E> {
+    fn localize(&self, f: &mut fmt::Formatter<'_>, catalog: &dyn Catalog) -> fmt::Result {
+        if let Some(code) = self.code() {
+            write!(f, "{}: ", code)?;
+        }
+
+        self.error.localize(f, catalog)?;
+        self.localize_details(f, catalog)
+    }
+}
+
+impl<E: Localize> Localize for Failure<E> {
+    fn localize(&self, f: &mut fmt::Formatter<'_>, catalog: &dyn Catalog) -> fmt::Result {
+        match self {
+            Failure::Error(e) => e.localize(f, catalog),
+            Failure::Elided => catalog.message(f, "error-elided", &[]),
+        }
+    }
+}
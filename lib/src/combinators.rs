@@ -1,6 +1,6 @@
 use std::default::Default;
 
-use crate::input::{Pear, Input, Rewind, Token, Result};
+use crate::input::{Pear, Input, Rewind, Token, Expected, Result};
 use crate::macros::parser;
 use crate::parsers::*;
 
@@ -13,20 +13,40 @@ pub trait Collection<A>: Default + Extend<A> {
 
 impl<A, T: Default + Extend<A>> Collection<A> for T {  }
 
-pub fn ok<I, P, O>(input: &mut Pear<I>, p: P) -> Option<O>
-    where I: Input, P: FnOnce(&mut Pear<I>) -> Result<O, I>
+/// Runs `p`, returning `Some` if it succeeds or `None` otherwise. A fatal
+/// failure is discarded like any other; use [`attempt()`] to propagate it.
+pub fn ok<I, P, O, U, E>(input: &mut Pear<I, U>, p: P) -> Option<O>
+    where I: Input, P: FnOnce(&mut Pear<I, U>) -> Result<O, I, E>
 {
-    let save = input.emit_error;
-    input.emit_error = false;
+    let fatal = input.fatal;
+    let save = input.speculate();
     let ok = p(input).ok();
     input.emit_error = save;
+    input.fatal = fatal;
     ok
 }
 
+/// Runs `p`, returning `Some` if it succeeds or `None` if it fails. Fails if
+/// `p` fails fatally.
+pub fn attempt<I, P, O, U, E>(input: &mut Pear<I, U>, p: P) -> Result<Option<O>, I, E>
+    where I: Input, P: FnOnce(&mut Pear<I, U>) -> Result<O, I, E>
+{
+    let save = input.speculate();
+    let result = p(input);
+    input.emit_error = save;
+    match result {
+        Ok(output) => Ok(Some(output)),
+        Err(e) if input.is_fatal() => Err(e),
+        Err(_) => Ok(None),
+    }
+}
+
 /// Parses `p` until `p` fails, returning the last successful `p`.
 #[parser(raw)]
-pub fn last_of_many<I, O, P>(input: &mut Pear<I>, mut p: P) -> Result<O, I>
-    where I: Input, P: FnMut(&mut Pear<I>) -> Result<O, I>
+pub fn last_of_many<I, O, P, U, E>(input: &mut Pear<I, U>, mut p: P) -> Result<O, I, E>
+    where I: Input,
+          P: FnMut(&mut Pear<I, U>) -> Result<O, I, E>,
+          E: From<Expected<I>>
 {
     loop {
         let output = p()?;
@@ -38,10 +58,11 @@ pub fn last_of_many<I, O, P>(input: &mut Pear<I>, mut p: P) -> Result<O, I>
 
 /// Skips all tokens that match `f` before and after a `p`, returning `p`.
 #[parser(raw)]
-pub fn surrounded<I, O, F, P>(input: &mut Pear<I>, mut p: P, mut f: F) -> Result<O, I>
+pub fn surrounded<I, O, F, P, U, E>(input: &mut Pear<I, U>, mut p: P, mut f: F) -> Result<O, I, E>
     where I: Input,
           F: FnMut(&I::Token) -> bool,
-          P: FnMut(&mut Pear<I>) -> Result<O, I>
+          P: FnMut(&mut Pear<I, U>) -> Result<O, I, E>,
+          E: From<Expected<I>>
 {
     skip_while(&mut f)?;
     let output = p()?;
@@ -52,8 +73,11 @@ pub fn surrounded<I, O, F, P>(input: &mut Pear<I>, mut p: P, mut f: F) -> Result
 /// Parses as many `p` as possible until EOF is reached, collecting them into a
 /// `C`. Fails if `p` every fails. `C` may be empty.
 #[parser(raw)]
-pub fn collect<C, I, O, P>(input: &mut Pear<I>, mut p: P) -> Result<C, I>
-    where C: Collection<O>, I: Input, P: FnMut(&mut Pear<I>) -> Result<O, I>
+pub fn collect<C, I, O, P, U, E>(input: &mut Pear<I, U>, mut p: P) -> Result<C, I, E>
+    where C: Collection<O>,
+          I: Input,
+          P: FnMut(&mut Pear<I, U>) -> Result<O, I, E>,
+          E: From<Expected<I>>
 {
     let mut collection = C::default();
     loop {
@@ -68,8 +92,11 @@ pub fn collect<C, I, O, P>(input: &mut Pear<I>, mut p: P) -> Result<C, I>
 /// Parses as many `p` as possible until EOF is reached, collecting them into a
 /// `C`. Fails if `p` ever fails. `C` is not allowed to be empty.
 #[parser(raw)]
-pub fn collect_some<C, I, O, P>(input: &mut Pear<I>, mut p: P) -> Result<C, I>
-    where C: Collection<O>, I: Input, P: FnMut(&mut Pear<I>) -> Result<O, I>
+pub fn collect_some<C, I, O, P, U, E>(input: &mut Pear<I, U>, mut p: P) -> Result<C, I, E>
+    where C: Collection<O>,
+          I: Input,
+          P: FnMut(&mut Pear<I, U>) -> Result<O, I, E>,
+          E: From<Expected<I>>
 {
     let mut collection = C::default();
     loop {
@@ -81,10 +108,14 @@ pub fn collect_some<C, I, O, P>(input: &mut Pear<I>, mut p: P) -> Result<C, I>
 }
 
 /// Parses as many `p` as possible until EOF is reached or `p` fails, collecting
-/// them into a `C`. `C` may be empty.
+/// them into a `C`. `C` may be empty. If `p` fails fatally, so does
+/// `try_collect`.
 #[parser(raw)]
-pub fn try_collect<C, I, O, P>(input: &mut Pear<I>, mut p: P) -> Result<C, I>
-    where C: Collection<O>, I: Input + Rewind, P: FnMut(&mut Pear<I>) -> Result<O, I>
+pub fn try_collect<C, I, O, P, U, E>(input: &mut Pear<I, U>, mut p: P) -> Result<C, I, E>
+    where C: Collection<O>,
+          I: Input + Rewind,
+          P: FnMut(&mut Pear<I, U>) -> Result<O, I, E>,
+          E: From<Expected<I>>
 {
     let mut collection = C::default();
     loop {
@@ -98,12 +129,17 @@ pub fn try_collect<C, I, O, P>(input: &mut Pear<I>, mut p: P) -> Result<C, I>
             raw: true
         });
 
-        match ok(input, |i| p(i)) {
+        let state = input.checkpoint_state();
+        let result = attempt(input, |i| p(i));
+        if !matches!(result, Ok(Some(_))) {
+            input.rewind_to(start);
+            input.restore_state(state);
+        }
+
+        input.release_state(state);
+        match result? {
             Some(val) => collection.push(val),
-            None => {
-                input.rewind_to(start);
-                break;
-            }
+            None => break,
         }
     }
 
@@ -115,18 +151,19 @@ pub fn try_collect<C, I, O, P>(input: &mut Pear<I>, mut p: P) -> Result<C, I>
 ///
 /// START (item SEPERATOR)* END
 #[parser(raw)]
-pub fn delimited_collect<C, I, T, S, O, P>(
-    input: &mut Pear<I>,
+pub fn delimited_collect<C, I, T, S, O, P, U, E>(
+    input: &mut Pear<I, U>,
     start: T,
     mut item: P,
     separator: S,
     end: T,
-) -> Result<C, I>
+) -> Result<C, I, E>
     where C: Collection<O>,
           I: Input,
           T: Token<I> + Clone,
           S: Into<Option<T>>,
-          P: FnMut(&mut Pear<I>) -> Result<O, I>,
+          P: FnMut(&mut Pear<I, U>) -> Result<O, I, E>,
+          E: From<Expected<I>>,
 {
     eat(start)?;
 
@@ -154,15 +191,16 @@ pub fn delimited_collect<C, I, T, S, O, P>(
 ///
 /// item (SEPERATOR item)*
 #[parser(raw)]
-pub fn series<C, I, S, O, P>(
-    input: &mut Pear<I>,
+pub fn series<C, I, S, O, P, U, E>(
+    input: &mut Pear<I, U>,
     mut item: P,
     seperator: S,
-) -> Result<C, I>
+) -> Result<C, I, E>
     where C: Collection<O>,
           I: Input,
           S: Token<I> + Clone,
-          P: FnMut(&mut Pear<I>) -> Result<O, I>,
+          P: FnMut(&mut Pear<I, U>) -> Result<O, I, E>,
+          E: From<Expected<I>>,
 {
     let mut collection = C::default();
     loop {
@@ -180,21 +218,22 @@ pub fn series<C, I, S, O, P>(
 ///
 /// item (SEPERATOR item)* SEPERATOR?
 #[parser(raw)]
-pub fn trailing_series<C, I, S, O, P>(
-    input: &mut Pear<I>,
+pub fn trailing_series<C, I, S, O, P, U, E>(
+    input: &mut Pear<I, U>,
     mut item: P,
     seperator: S,
-) -> Result<C, I>
+) -> Result<C, I, E>
     where C: Collection<O>,
           I: Input,
           S: Token<I> + Clone,
-          P: FnMut(&mut Pear<I>) -> Result<O, I>,
+          P: FnMut(&mut Pear<I, U>) -> Result<O, I, E>,
+          E: From<Expected<I>>,
 {
     let mut collection = C::default();
     let mut have_some = false;
     loop {
         if have_some {
-            if let Some(item) = ok(input, |i| item(i)) {
+            if let Some(item) = attempt(|i| item(i))? {
                 collection.push(item);
             } else {
                 break
@@ -217,16 +256,17 @@ pub fn trailing_series<C, I, S, O, P>(
 ///
 /// PREFIX (item SEPERATOR)*
 #[parser(raw)]
-pub fn prefixed_series<C, I, T, O, P>(
-    input: &mut Pear<I>,
+pub fn prefixed_series<C, I, T, O, P, U, E>(
+    input: &mut Pear<I, U>,
     prefix: T,
     item: P,
     seperator: T,
-) -> Result<C, I>
+) -> Result<C, I, E>
     where C: Collection<O>,
           I: Input,
           T: Token<I> + Clone,
-          P: FnMut(&mut Pear<I>) -> Result<O, I>,
+          P: FnMut(&mut Pear<I, U>) -> Result<O, I, E>,
+          E: From<Expected<I>>,
 {
     if ok(input, |i| eat(i, prefix)).is_none() {
         return Ok(C::default());
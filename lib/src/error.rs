@@ -1,53 +1,507 @@
-use crate::input::{Show, ParserInfo};
+use std::borrow::Cow;
+
+use crate::input::{Show, ParserInfo, IntoOwned, StackPolicy};
+use crate::catalog::{Catalog, English, Args};
 
 pub use crate::expected::Expected;
 
+/// An error `E` from a failed parser, the context `C` in which it failed, and
+/// the contexts of the parsers it was called from.
+///
+/// Built-in parsers fail with an [`Expected`]. Parsers may instead fail with
+/// a custom error type, such as an `enum` of domain errors, which callers can
+/// then match on. `?` converts the error of a parser into the calling
+/// parser's error type via `From`, so a custom error type composes with the
+/// built-in parsers if it implements `From<Expected<..>>`:
+///
+/// ```rust
+/// use pear::input::{Pear, Text, Expected};
+/// use pear::macros::{parser, parse_error};
+/// use pear::parsers::*;
+///
+/// #[derive(Debug)]
+/// enum Error<'a> {
+///     Expected(Expected<Text<'a>>),
+///     BadNumber(&'a str),
+/// }
+///
+/// impl<'a> From<Expected<Text<'a>>> for Error<'a> {
+///     fn from(expected: Expected<Text<'a>>) -> Self {
+///         Error::Expected(expected)
+///     }
+/// }
+///
+/// type Result<'a, T> = pear::input::Result<T, Text<'a>, Error<'a>>;
+///
+/// #[parser]
+/// fn byte<'a>(input: &mut Pear<Text<'a>>) -> Result<'a, u8> {
+///     let digits = take_some_while(|c| c.is_ascii_digit())?;
+///     match digits.parse::<u8>() {
+///         Ok(byte) => byte,
+///         Err(_) => parse_error!(Error::BadNumber(digits))?,
+///     }
+/// }
+///
+/// assert_eq!(byte(&mut Pear::new("255")).unwrap(), 255);
+///
+/// let error = byte(&mut Pear::new("256")).unwrap_err().unwrap();
+/// assert!(matches!(error.error, Error::BadNumber("256")));
+///
+/// let error = byte(&mut Pear::new("x")).unwrap_err().unwrap();
+/// assert!(matches!(error.error, Error::Expected(_)));
+/// ```
+///
+/// # Serialization
+///
+/// With the `serde` feature enabled, `ParseError`, its contexts, and
+/// [`Expected`] implement `Serialize` and `Deserialize`. The shape below is
+/// stable. When `eat('"')` fails in a `string` parser called from an
+/// `object` parser over [`Text`](crate::input::Text), the error serializes
+/// to JSON as:
+///
+/// ```json
+/// {
+///   "error": { "kind": "token", "expected": "'\"'", "found": "x" },
+///   "info": {
+///     "parser": { "name": "eat", "raw": true },
+///     "context": { "start": [1, 6, 5], "end": [1, 6, 5], "cursor": "x", "snippet": "" }
+///   },
+///   "stack": [
+///     {
+///       "parser": { "name": "string", "raw": false },
+///       "context": { "start": [1, 6, 5], "end": [1, 6, 5], "cursor": "x", "snippet": "" }
+///     },
+///     {
+///       "parser": { "name": "object", "raw": false },
+///       "context": { "start": [1, 1, 0], "end": [1, 6, 5], "cursor": "x", "snippet": "{\"a\":" }
+///     }
+///   ],
+///   "diagnostic": { "code": "E0001", "help": null, "notes": [], "labels": [], "suggestions": [] }
+/// }
+/// ```
+///
+///   * `stack` lists the contexts of the calling parsers, innermost first.
+///     `raw` parsers are the built-in parsers in [`parsers`](crate::parsers).
+///     An entry collapsed from several by a
+///     [`StackPolicy`](crate::input::StackPolicy) has a `count`; others
+///     omit it.
+///   * Positions are `[line, column, offset]`: lines and columns count from
+///     1, offsets are in bytes from 0. `end` is exclusive.
+///   * `diagnostic` is omitted when there is no diagnostic metadata. Each
+///     label and suggestion has a `context` and a `message` or
+///     `replacement`.
+///   * `error` is tagged by `kind`, one of `token`, `slice`, `eof`,
+///     `incomplete`, `malformed`, `too_deep`, `limit_exceeded`, `foreign`,
+///     `other` or `elided`. See [`Expected`] for the fields of each.
+///
+/// The contexts of other inputs serialize as their own type does: a
+/// [`Cursor`](crate::input::Cursor), for instance, as an `{"start", "end",
+/// "values"}` extent. A [`Span`](crate::input::Span) borrows its snippet, so
+/// deserialize errors with an [`OwnedSpan`](crate::input::OwnedSpan) context
+/// unless the snippet is known to need no unescaping.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound(deserialize = "C: serde::Deserialize<'de>, E: serde::Deserialize<'de>")))]
 pub struct ParseError<C, E> {
     pub error: E,
     pub info: ErrorInfo<C>,
     pub stack: Vec<ErrorInfo<C>>,
+    /// Diagnostic metadata such as an error code, help and notes, if any.
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub diagnostic: Option<Box<Diagnostic<C>>>,
 }
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ErrorInfo<C> {
     pub parser: ParserInfo,
     pub context: C,
+    /// The number of consecutive contexts of `parser` this entry stands for.
+    /// Greater than 1 only if collapsed by [`StackPolicy::collapse`].
+    #[cfg_attr(feature = "serde", serde(default = "crate::serialization::one"))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "crate::serialization::is_one"))]
+    pub count: usize,
 }
 
 impl<C> ErrorInfo<C> {
     pub fn new(parser: ParserInfo, context: C) -> Self {
-        Self { parser, context }
+        Self { parser, context, count: 1 }
+    }
+}
+
+impl<C: IntoOwned> IntoOwned for ErrorInfo<C> {
+    type Owned = ErrorInfo<C::Owned>;
+
+    #[inline(always)]
+    fn into_owned(self) -> Self::Owned {
+        ErrorInfo { parser: self.parser, context: self.context.into_owned(), count: self.count }
+    }
+}
+
+/// Diagnostic metadata attached to a [`ParseError`]: an error code, a help
+/// message, notes, labeled secondary contexts, and suggested fixes.
+///
+/// Attach metadata with [`parse_error!`](crate::macros::parse_error) or the
+/// `with_` methods on `ParseError`. [`ParseError`]'s `Display` renders it as:
+///
+/// ```text
+/// E0042: duplicate section `a`
+///  + section 3:1 to 3:4 "[a]"
+///  - first defined here 1:1 to 1:4 "[a]"
+///  = note: section names are case-sensitive
+///  = help: rename one of the sections
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Diagnostic<C> {
+    pub code: Option<Cow<'static, str>>,
+    pub help: Option<Cow<'static, str>>,
+    pub notes: Vec<Cow<'static, str>>,
+    pub labels: Vec<Label<C>>,
+    pub suggestions: Vec<Suggestion<C>>,
+}
+
+/// A message about a secondary context of an error, such as a previous
+/// definition.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Label<C> {
+    pub context: C,
+    pub message: Cow<'static, str>,
+}
+
+/// A machine-applicable fix for an error: replacing the input spanned by
+/// `context` with `replacement`.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Suggestion<C> {
+    pub context: C,
+    pub replacement: Cow<'static, str>,
+}
+
+impl<C> Default for Diagnostic<C> {
+    fn default() -> Self {
+        Diagnostic { code: None, help: None, notes: vec![], labels: vec![], suggestions: vec![] }
+    }
+}
+
+impl<C: IntoOwned> IntoOwned for Diagnostic<C> {
+    type Owned = Diagnostic<C::Owned>;
+
+    fn into_owned(self) -> Self::Owned {
+        Diagnostic {
+            code: self.code,
+            help: self.help,
+            notes: self.notes,
+            labels: self.labels.into_iter()
+                .map(|l| Label { context: l.context.into_owned(), message: l.message })
+                .collect(),
+            suggestions: self.suggestions.into_iter()
+                .map(|s| Suggestion { context: s.context.into_owned(), replacement: s.replacement })
+                .collect(),
+        }
     }
 }
 
 impl<C, E> ParseError<C, E> {
     pub fn new(parser: ParserInfo, error: E, context: C) -> ParseError<C, E> {
-        ParseError { error, info: ErrorInfo::new(parser, context), stack: vec![] }
+        ParseError {
+            error,
+            info: ErrorInfo::new(parser, context),
+            stack: vec![],
+            diagnostic: None,
+        }
+    }
+
+    /// Returns the error's code, if any.
+    pub fn code(&self) -> Option<&str> {
+        self.diagnostic.as_ref()?.code.as_deref()
+    }
+
+    /// Returns the error's help message, if any.
+    pub fn help(&self) -> Option<&str> {
+        self.diagnostic.as_ref()?.help.as_deref()
+    }
+
+    /// Returns the error's notes.
+    pub fn notes(&self) -> &[Cow<'static, str>] {
+        self.diagnostic.as_ref().map(|d| &d.notes[..]).unwrap_or(&[])
+    }
+
+    /// Returns the error's labeled secondary contexts.
+    pub fn labels(&self) -> &[Label<C>] {
+        self.diagnostic.as_ref().map(|d| &d.labels[..]).unwrap_or(&[])
+    }
+
+    /// Returns the suggested fixes for the error. See
+    /// [`Pear::with_suggestions()`](crate::input::Pear::with_suggestions()).
+    pub fn suggestions(&self) -> &[Suggestion<C>] {
+        self.diagnostic.as_ref().map(|d| &d.suggestions[..]).unwrap_or(&[])
+    }
+
+    /// Returns the error's diagnostic metadata, creating it if there is none.
+    pub fn diagnostic_mut(&mut self) -> &mut Diagnostic<C> {
+        self.diagnostic.get_or_insert_with(Default::default)
+    }
+
+    /// Sets the error's code, such as `E0042`.
+    pub fn with_code<S: Into<Cow<'static, str>>>(mut self, code: S) -> Self {
+        self.diagnostic_mut().code = Some(code.into());
+        self
+    }
+
+    /// Sets the error's help message.
+    pub fn with_help<S: Into<Cow<'static, str>>>(mut self, help: S) -> Self {
+        self.diagnostic_mut().help = Some(help.into());
+        self
+    }
+
+    /// Adds a note to the error.
+    pub fn with_note<S: Into<Cow<'static, str>>>(mut self, note: S) -> Self {
+        self.diagnostic_mut().notes.push(note.into());
+        self
+    }
+
+    /// Adds a label with `message` pointing at `context`.
+    pub fn with_label<S: Into<Cow<'static, str>>>(mut self, context: C, message: S) -> Self {
+        let message = message.into();
+        self.diagnostic_mut().labels.push(Label { context, message });
+        self
     }
 
     pub fn push_info(&mut self, parser: ParserInfo, context: C) {
         self.stack.push(ErrorInfo::new(parser, context));
     }
 
+    /// Pushes the context of `parser` onto the stack if `policy` keeps it,
+    /// calling `context` only if a new entry is pushed.
+    pub fn push_info_with<F>(&mut self, parser: ParserInfo, policy: StackPolicy, context: F)
+        where F: FnOnce() -> C
+    {
+        if policy.hide_raw && parser.raw {
+            return;
+        }
+
+        if policy.collapse {
+            if let Some(last) = self.stack.last_mut().filter(|i| i.parser.name == parser.name) {
+                last.count += 1;
+                return;
+            }
+        }
+
+        if policy.max_frames.map_or(true, |max| self.stack.len() < max) {
+            self.push_info(parser, context());
+        }
+    }
+
     #[inline(always)]
     pub fn into<E2: From<E>>(self) -> ParseError<C, E2> {
         ParseError {
             error: self.error.into(),
             info: self.info,
             stack: self.stack,
+            diagnostic: self.diagnostic,
+        }
+    }
+
+    /// Converts the error and every context into a version that doesn't
+    /// borrow from the input, so that the error can outlive it, be sent
+    /// across threads, or be boxed into a `Box<dyn std::error::Error>`.
+    ///
+    /// ```rust
+    /// use pear::input::{Pear, Text, OwnedSpan};
+    /// use pear::error::{ParseError, Expected};
+    /// use pear::parsers::eat;
+    ///
+    /// fn parse(string: &str) -> Result<char, ParseError<OwnedSpan, Expected<char, String>>> {
+    ///     eat(&mut Pear::<Text<'_>>::new(string), 'a').map_err(|e| e.unwrap().into_owned())
+    /// }
+    ///
+    /// let error: Box<dyn std::error::Error> = Box::new(parse(&String::from("b")).unwrap_err());
+    /// assert!(error.to_string().starts_with("expected token 'a' but found 'b'"));
+    /// ```
+    pub fn into_owned(self) -> ParseError<C::Owned, E::Owned>
+        where C: IntoOwned, E: IntoOwned
+    {
+        ParseError {
+            error: self.error.into_owned(),
+            info: self.info.into_owned(),
+            stack: self.stack.into_iter().map(|i| i.into_owned()).collect(),
+            diagnostic: self.diagnostic.map(|d| Box::new(d.into_owned())),
         }
     }
 }
 
+impl<C: IntoOwned, E: IntoOwned> IntoOwned for ParseError<C, E> {
+    type Owned = ParseError<C::Owned, E::Owned>;
+
+    #[inline(always)]
+    fn into_owned(self) -> Self::Owned {
+        ParseError::into_owned(self)
+    }
+}
+
+impl<C: Show, E> ParseError<C, E> {
+    /// Writes the stack, label, note, and help lines of this error, with the
+    /// messages of `catalog`.
+    pub(crate) fn localize_details(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+        catalog: &dyn Catalog
+    ) -> std::fmt::Result {
+        for info in &self.stack {
+            let (parser, context) = (&info.parser.name, &info.context as &dyn Show);
+            write!(f, "\n + ")?;
+            match info.count {
+                1 => catalog.message(f, "stack", &[("parser", parser), ("context", &context)])?,
+                n => {
+                    let args: &Args<'_> = &[("parser", parser), ("count", &n), ("context", &context)];
+                    catalog.message(f, "stack-repeated", args)?
+                }
+            }
+        }
+
+        for label in self.labels() {
+            let (message, context) = (&label.message, &label.context as &dyn Show);
+            write!(f, "\n - ")?;
+            catalog.message(f, "label", &[("message", message), ("context", &context)])?;
+        }
+
+        for note in self.notes() {
+            write!(f, "\n = ")?;
+            catalog.message(f, "note", &[("note", note)])?;
+        }
+
+        if let Some(help) = self.help() {
+            write!(f, "\n = ")?;
+            catalog.message(f, "help", &[("help", &help)])?;
+        }
+
+        Ok(())
+    }
+}
+
 impl<C: Show, E: std::fmt::Display> std::fmt::Display for ParseError<C, E> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(code) = self.code() {
+            write!(f, "{}: ", code)?;
+        }
+
         write!(f, "{}", self.error)?;
-        for info in &self.stack {
-            write!(f, "\n + {}", info.parser.name)?;
-            write!(f, " {}", &info.context as &dyn Show)?;
+        self.localize_details(f, &English)
+    }
+}
+
+impl<C, E> std::error::Error for ParseError<C, E>
+    where C: Show + std::fmt::Debug, E: std::error::Error
+{
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        self.error.source()
+    }
+}
+
+/// The error of a failed parser: either an [`Elided`](Failure::Elided) failure
+/// that carries no details, or an [`Error`](Failure::Error).
+///
+/// A parser that fails in a speculative branch, such as a `switch!` arm,
+/// [`parse_try!`], or [`ok()`], fails with `Failure::Elided` when its error
+/// won't be reported: no [`ParseError`] is built for it, so no context is
+/// taken and no message is formatted. Only a failure that may be reported is
+/// a `Failure::Error`. See [`Result`](crate::result::Result).
+///
+/// ```rust
+/// use pear::input::{Pear, Text};
+/// use pear::error::{Failure, Expected};
+/// use pear::parsers::eat;
+///
+/// let failure = eat(&mut Pear::<Text<'_>>::new("b"), 'a').unwrap_err();
+/// assert!(matches!(failure, Failure::Error(ref e) if matches!(e.error, Expected::Token(..))));
+/// assert!(failure.unwrap().to_string().starts_with("expected token 'a'"));
+/// ```
+///
+/// [`parse_try!`]: crate::macros::parse_try
+/// [`ok()`]: crate::combinators::ok()
+#[derive(Debug, Clone)]
+pub enum Failure<E> {
+    /// A failure whose error won't be reported.
+    Elided,
+    /// A failure with its error.
+    Error(E),
+}
+
+impl<E> Failure<E> {
+    /// Returns `true` if the failure is [`Failure::Elided`].
+    #[inline(always)]
+    pub fn is_elided(&self) -> bool {
+        matches!(self, Failure::Elided)
+    }
+
+    /// Returns the error, if the failure isn't elided.
+    #[inline(always)]
+    pub fn error(self) -> Option<E> {
+        match self {
+            Failure::Error(e) => Some(e),
+            Failure::Elided => None,
         }
+    }
 
-        Ok(())
+    /// Returns a reference to the error, if the failure isn't elided.
+    #[inline(always)]
+    pub fn as_error(&self) -> Option<&E> {
+        match self {
+            Failure::Error(e) => Some(e),
+            Failure::Elided => None,
+        }
+    }
+
+    /// Returns the error.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the failure is elided.
+    #[track_caller]
+    pub fn unwrap(self) -> E {
+        match self {
+            Failure::Error(e) => e,
+            Failure::Elided => panic!("called `Failure::unwrap()` on an elided failure"),
+        }
+    }
+
+    /// Maps the error with `f`, leaving an elided failure as is.
+    #[inline(always)]
+    pub fn map<F, G: FnOnce(E) -> F>(self, f: G) -> Failure<F> {
+        match self {
+            Failure::Error(e) => Failure::Error(f(e)),
+            Failure::Elided => Failure::Elided,
+        }
+    }
+}
+
+impl<C, E> Failure<ParseError<C, E>> {
+    #[inline(always)]
+    pub fn into<E2: From<E>>(self) -> Failure<ParseError<C, E2>> {
+        self.map(|e| e.into())
+    }
+}
+
+impl<E> From<E> for Failure<E> {
+    #[inline(always)]
+    fn from(error: E) -> Self {
+        Failure::Error(error)
+    }
+}
+
+impl<E: std::fmt::Display> std::fmt::Display for Failure<E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Failure::Error(e) => e.fmt(f),
+            Failure::Elided => English.message(f, "error-elided", &[]),
+        }
+    }
+}
+
+impl<E: std::error::Error> std::error::Error for Failure<E> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        self.as_error()?.source()
     }
 }
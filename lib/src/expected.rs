@@ -1,8 +1,10 @@
 use std::fmt;
+use std::sync::Arc;
 
 use inlinable_string::InlinableString;
 
-use crate::input::Show;
+use crate::input::{Show, Limit, IntoOwned};
+use crate::catalog::{Localize, English};
 
 #[derive(Clone)]
 pub enum CowInlineString {
@@ -32,11 +34,41 @@ impl std::fmt::Debug for CowInlineString {
     }
 }
 
+/// The error of the built-in parsers: what was expected, and what was found.
+///
+/// # Serialization
+///
+/// With the `serde` feature enabled, an `Expected` serializes as an object
+/// tagged by `kind`. Expected tokens and slices are rendered to strings;
+/// foreign errors are reduced to their message and deserialize as an opaque
+/// error with the same `Display`.
+///
+/// | `kind`           | fields                                                    |
+/// |------------------|-----------------------------------------------------------|
+/// | `token`          | `expected`: string or null, `found`: token or null        |
+/// | `slice`          | `expected`: string or null, `found`: slice or null        |
+/// | `eof`            | `found`: token or null                                    |
+/// | `incomplete`     | `needed`: integer or null                                 |
+/// | `malformed`      | `encoding`: string, `offset`: integer                     |
+/// | `too_deep`       | `limit`: integer                                          |
+/// | `limit_exceeded` | `limit`: `"fuel"`, `"cancelled"` or `{"input_length": n}` |
+/// | `foreign`        | `message`: string                                         |
+/// | `other`          | `message`: string                                         |
+/// | `elided`         |                                                           |
 pub enum Expected<Token, Slice> {
     Token(Option<InlinableString>, Option<Token>),
     Slice(Option<InlinableString>, Option<Slice>),
     Eof(Option<Token>),
+    Incomplete { needed: Option<usize> },
+    Malformed { encoding: &'static str, offset: usize },
+    TooDeep { limit: usize },
+    LimitExceeded(Limit),
+    /// An error from a fallible operation other than a parser, such as
+    /// `str::parse()`, propagated with `?` in a `#[parser]`.
+    Foreign(Arc<dyn std::error::Error + Send + Sync>),
     Other(CowInlineString),
+    /// The error of a failure that won't be reported, in place of a message
+    /// that wasn't formatted. See [`Result`](crate::result::Result).
     Elided
 }
 
@@ -50,6 +82,11 @@ impl<Token, Slice> Expected<Token, Slice> {
             Token(e, v) => Token(e, v.map(t)),
             Slice(e, v) => Slice(e, v.map(s)),
             Eof(v) => Eof(v.map(t)),
+            Incomplete { needed } => Incomplete { needed },
+            Malformed { encoding, offset } => Malformed { encoding, offset },
+            TooDeep { limit } => TooDeep { limit },
+            LimitExceeded(limit) => LimitExceeded(limit),
+            Foreign(e) => Foreign(e),
             Other(v) => Other(v),
             Expected::Elided => Expected::Elided,
         }
@@ -62,6 +99,15 @@ impl<T: ToOwned, S: ?Sized + ToOwned> Expected<T, &S> {
     }
 }
 
+impl<T: IntoOwned, S: IntoOwned> IntoOwned for Expected<T, S> {
+    type Owned = Expected<T::Owned, S::Owned>;
+
+    #[inline(always)]
+    fn into_owned(self) -> Self::Owned {
+        self.map(IntoOwned::into_owned, IntoOwned::into_owned)
+    }
+}
+
 impl<T, S> From<String> for Expected<T, S> {
     #[inline(always)]
     fn from(string: String) -> Expected<T, S> {
@@ -96,6 +142,24 @@ impl<T: fmt::Debug, S: fmt::Debug> fmt::Debug for Expected<T, S> {
             Expected::Eof(v) => {
                 f.debug_tuple("Expected::Eof").field(&v).finish()
             }
+            Expected::Incomplete { needed } => {
+                f.debug_struct("Expected::Incomplete").field("needed", &needed).finish()
+            }
+            Expected::Malformed { encoding, offset } => {
+                f.debug_struct("Expected::Malformed")
+                    .field("encoding", &encoding)
+                    .field("offset", &offset)
+                    .finish()
+            }
+            Expected::TooDeep { limit } => {
+                f.debug_struct("Expected::TooDeep").field("limit", &limit).finish()
+            }
+            Expected::LimitExceeded(limit) => {
+                f.debug_tuple("Expected::LimitExceeded").field(&limit).finish()
+            }
+            Expected::Foreign(e) => {
+                f.debug_tuple("Expected::Foreign").field(&e).finish()
+            }
             Expected::Other(v) => {
                 f.debug_tuple("Expected::Other").field(&v).finish()
             }
@@ -110,6 +174,13 @@ impl<T: Clone, S: Clone> Clone for Expected<T, S> {
             Expected::Token(e, f) => Expected::Token(e.clone(), f.clone()),
             Expected::Slice(e, f) => Expected::Slice(e.clone(), f.clone()),
             Expected::Eof(f) => Expected::Eof(f.clone()),
+            Expected::Incomplete { needed } => Expected::Incomplete { needed: *needed },
+            Expected::Malformed { encoding, offset } => {
+                Expected::Malformed { encoding, offset: *offset }
+            }
+            Expected::TooDeep { limit } => Expected::TooDeep { limit: *limit },
+            Expected::LimitExceeded(limit) => Expected::LimitExceeded(*limit),
+            Expected::Foreign(e) => Expected::Foreign(e.clone()),
             Expected::Other(v) => Expected::Other(v.clone()),
             Expected::Elided => Expected::Elided,
         }
@@ -118,44 +189,15 @@ impl<T: Clone, S: Clone> Clone for Expected<T, S> {
 
 impl<T: Show, S: Show> fmt::Display for Expected<T, S> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match *self {
-            Expected::Token(Some(ref expected), Some(ref found)) => {
-                let found = found as &dyn Show;
-                write!(f, "expected token {} but found {}", expected, found)
-            }
-            Expected::Token(None, Some(ref found)) => {
-                let found = found as &dyn Show;
-                write!(f, "unexpected token: {}", found)
-            }
-            Expected::Token(Some(ref expected), None) => {
-                write!(f, "expected token {} but none was found", expected)
-            }
-            Expected::Token(None, None) => {
-                write!(f, "expected any token but none was found")
-            }
-            Expected::Slice(Some(ref expected), Some(ref found)) => {
-                let found = found as &dyn Show;
-                write!(f, "expected slice {} but found {}", expected, found)
-            }
-            Expected::Slice(None, Some(ref found)) => {
-                let found = found as &dyn Show;
-                write!(f, "unexpected slice: {}", found)
-            }
-            Expected::Slice(Some(ref expected), None) => {
-                write!(f, "expected slice {} but none was found", expected)
-            }
-            Expected::Slice(None, None) => {
-                write!(f, "expected any slice but none was found")
-            }
-            Expected::Eof(None) => {
-                write!(f, "expected EOF but input remains")
-            }
-            Expected::Eof(Some(ref found)) => {
-                let found = found as &dyn Show;
-                write!(f, "expected EOF but found {}", found)
-            }
-            Expected::Other(ref other) => write!(f, "{}", other),
-            Expected::Elided => write!(f, "[ERROR ELIDED]")
+        self.localize(f, &English)
+    }
+}
+
+impl<T: Show + fmt::Debug, S: Show + fmt::Debug> std::error::Error for Expected<T, S> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Expected::Foreign(e) => Some(&**e),
+            _ => None,
         }
     }
 }
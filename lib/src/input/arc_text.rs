@@ -0,0 +1,309 @@
+use std::fmt;
+use std::ops::Range;
+use std::sync::Arc;
+
+use crate::input::{Input, Rewind, ParserInfo, Show, Length, OwnedSpan, IntoOwned};
+use crate::input::text::LineCache;
+
+/// A cheaply cloneable, owned string slice: an `Arc<str>` and a range into it.
+///
+/// Produced by [`ArcText`]. Cloning an `ArcStr` only bumps a reference count.
+/// Dereferences to the `str` it spans.
+#[derive(Clone)]
+pub struct ArcStr {
+    source: Arc<str>,
+    range: Range<usize>,
+}
+
+impl ArcStr {
+    /// Returns the string this slice spans.
+    pub fn as_str(&self) -> &str {
+        &self.source[self.range.clone()]
+    }
+
+    /// Returns the byte range of this slice in its source.
+    pub fn range(&self) -> Range<usize> {
+        self.range.clone()
+    }
+
+    /// Returns the full source string this slice was taken from.
+    pub fn source(&self) -> &Arc<str> {
+        &self.source
+    }
+}
+
+impl From<&str> for ArcStr {
+    fn from(string: &str) -> ArcStr {
+        ArcStr { range: 0..string.len(), source: string.into() }
+    }
+}
+
+impl std::ops::Deref for ArcStr {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl AsRef<str> for ArcStr {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl AsRef<[u8]> for ArcStr {
+    fn as_ref(&self) -> &[u8] {
+        self.as_str().as_bytes()
+    }
+}
+
+impl std::borrow::Borrow<str> for ArcStr {
+    fn borrow(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl PartialEq for ArcStr {
+    fn eq(&self, other: &ArcStr) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl Eq for ArcStr { }
+
+impl std::hash::Hash for ArcStr {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.as_str().hash(state)
+    }
+}
+
+impl PartialEq<str> for ArcStr {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialEq<&str> for ArcStr {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl PartialEq<ArcStr> for &str {
+    fn eq(&self, other: &ArcStr) -> bool {
+        *self == other.as_str()
+    }
+}
+
+impl fmt::Debug for ArcStr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl fmt::Display for ArcStr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self.as_str(), f)
+    }
+}
+
+impl IntoOwned for ArcStr {
+    type Owned = ArcStr;
+
+    #[inline(always)]
+    fn into_owned(self) -> ArcStr {
+        self
+    }
+}
+
+impl Show for ArcStr {
+    #[inline(always)]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Show::fmt(&self.as_str(), f)
+    }
+
+    #[inline(always)]
+    fn literal(&self) -> Option<String> {
+        Some(self.as_str().to_string())
+    }
+}
+
+impl Length for ArcStr {
+    #[inline(always)]
+    fn len(&self) -> usize {
+        self.range.len()
+    }
+}
+
+/// A text input that owns its source.
+///
+/// `ArcText` behaves like [`Text`](crate::input::Text), but its slices are
+/// [`ArcStr`]s that share ownership of the source and its contexts are
+/// [`OwnedSpan`]s. Neither borrows from the input, so parse results and
+/// errors are `'static` and can outlive the input, be cached, or be sent
+/// across threads.
+///
+/// ```rust
+/// use pear::input::{Pear, ArcText, ArcStr};
+/// use pear::parsers::*;
+///
+/// fn first_word(source: String) -> ArcStr {
+///     let mut input = Pear::from(ArcText::from(source));
+///     take_while(&mut input, |c| !c.is_whitespace()).unwrap()
+/// }
+///
+/// let word = first_word("hello, world".to_string());
+/// assert_eq!(word, "hello,");
+/// ```
+#[derive(Debug, Clone)]
+pub struct ArcText {
+    source: Arc<str>,
+    offset: usize,
+    lines: LineCache,
+}
+
+impl From<Arc<str>> for ArcText {
+    fn from(source: Arc<str>) -> ArcText {
+        ArcText { source, offset: 0, lines: LineCache::new() }
+    }
+}
+
+impl From<String> for ArcText {
+    fn from(source: String) -> ArcText {
+        ArcText::from(Arc::<str>::from(source))
+    }
+}
+
+impl From<&str> for ArcText {
+    fn from(source: &str) -> ArcText {
+        ArcText::from(Arc::<str>::from(source))
+    }
+}
+
+impl ArcText {
+    fn current(&self) -> &str {
+        &self.source[self.offset..]
+    }
+
+    fn arc_str(&self, start: usize, end: usize) -> ArcStr {
+        ArcStr { source: self.source.clone(), range: start..end }
+    }
+}
+
+impl Rewind for ArcText {
+    fn rewind_to(&mut self, marker: Self::Marker) {
+        self.offset = marker;
+    }
+}
+
+impl Input for ArcText {
+    type Token = char;
+    type Slice = ArcStr;
+    type Many = Self::Slice;
+
+    type Marker = usize;
+    type Context = OwnedSpan;
+
+    /// Returns a copy of the current token, if there is one.
+    fn token(&mut self) -> Option<Self::Token> {
+        self.current().chars().next()
+    }
+
+    /// Returns a copy of the current slice of size `n`, if there is one.
+    fn slice(&mut self, n: usize) -> Option<Self::Slice> {
+        self.current().get(..n)?;
+        Some(self.arc_str(self.offset, self.offset + n))
+    }
+
+    /// Checks if the current token fulfills `cond`.
+    fn peek<F>(&mut self, mut cond: F) -> bool
+        where F: FnMut(&Self::Token) -> bool
+    {
+        self.token().map(|t| cond(&t)).unwrap_or(false)
+    }
+
+    /// Checks if the current slice of size `n` (if any) fulfills `cond`.
+    fn peek_slice<F>(&mut self, n: usize, mut cond: F) -> bool
+        where F: FnMut(&Self::Slice) -> bool
+    {
+        self.slice(n).map(|s| cond(&s)).unwrap_or(false)
+    }
+
+    /// Checks if the current token fulfills `cond`. If so, the token is
+    /// consumed and returned. Otherwise, returns `None`.
+    fn eat<F>(&mut self, mut cond: F) -> Option<Self::Token>
+        where F: FnMut(&Self::Token) -> bool
+    {
+        let token = self.token()?;
+        if cond(&token) {
+            self.offset += token.len_utf8();
+            Some(token)
+        } else {
+            None
+        }
+    }
+
+    /// Checks if the current slice of size `n` (if any) fulfills `cond`. If so,
+    /// the slice is consumed and returned. Otherwise, returns `None`.
+    fn eat_slice<F>(&mut self, n: usize, mut cond: F) -> Option<Self::Slice>
+        where F: FnMut(&Self::Slice) -> bool
+    {
+        let slice = self.slice(n)?;
+        if cond(&slice) {
+            self.offset += n;
+            Some(slice)
+        } else {
+            None
+        }
+    }
+
+    /// Takes tokens while `cond` returns true, collecting them into a
+    /// `Self::Many` and returning it.
+    fn take<F>(&mut self, mut cond: F) -> Self::Many
+        where F: FnMut(&Self::Token) -> bool
+    {
+        let consumed: usize = self.current().chars()
+            .take_while(|c| cond(c))
+            .map(char::len_utf8)
+            .sum();
+
+        let start = self.offset;
+        self.offset += consumed;
+        self.arc_str(start, self.offset)
+    }
+
+    /// Skips tokens while `cond` returns true. Returns the number of skipped
+    /// tokens.
+    fn skip<F>(&mut self, cond: F) -> usize
+        where F: FnMut(&Self::Token) -> bool
+    {
+        self.take(cond).len()
+    }
+
+    /// Returns `true` if there are at least `n` tokens remaining.
+    fn has(&mut self, n: usize) -> bool {
+        self.current().len() >= n
+    }
+
+    #[inline(always)]
+    fn mark(&mut self, _: &ParserInfo) -> Self::Marker {
+        self.offset
+    }
+
+    fn context(&mut self, mark: Self::Marker) -> Self::Context {
+        let cursor = self.token();
+        if self.offset == 0 {
+            return OwnedSpan { start: (1, 1, 0), end: (1, 1, 0), snippet: None, cursor };
+        }
+
+        let (start_line, start_col) = self.lines.line_col(&*self.source, mark);
+        let (end_line, end_col) = self.lines.line_col(&*self.source, self.offset);
+        OwnedSpan {
+            start: (start_line, start_col, mark),
+            end: (end_line, end_col, self.offset),
+            snippet: Some(self.source[mark..self.offset].to_string()),
+            cursor,
+        }
+    }
+}
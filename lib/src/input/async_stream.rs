@@ -0,0 +1,196 @@
+use std::{fmt, io};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use crate::input::{self, Pear, Partial, Cursor, Extent, Stream, Status};
+use crate::error::{ParseError, Expected};
+
+/// The input type parsers run on when driven by an [`AsyncStream`].
+pub type AsyncInput<'a> = Partial<Cursor<&'a [u8]>>;
+
+/// A parse error with its contexts copied out of the stream's buffer.
+pub type OwnedParseError = ParseError<Extent<Vec<u8>>, Expected<u8, Extent<Vec<u8>>>>;
+
+/// An error returned by [`AsyncStream::parse()`].
+#[derive(Debug)]
+pub enum StreamError {
+    /// Reading from the underlying reader failed.
+    Io(io::Error),
+    /// The parser failed on the input read so far.
+    Parse(OwnedParseError),
+}
+
+impl From<io::Error> for StreamError {
+    fn from(error: io::Error) -> Self {
+        StreamError::Io(error)
+    }
+}
+
+impl fmt::Display for StreamError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StreamError::Io(e) => write!(f, "i/o error: {}", e),
+            StreamError::Parse(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for StreamError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            StreamError::Io(e) => Some(e),
+            StreamError::Parse(e) => Some(e),
+        }
+    }
+}
+
+/// A reader an [`AsyncStream`] reads from.
+///
+/// With the `tokio` feature, implemented for every `tokio::io::AsyncRead`.
+/// With the `futures-io` feature, implemented for a `futures::io::AsyncRead`
+/// wrapped in a [`FuturesRead`].
+pub trait AsyncSource: Unpin {
+    /// Attempts to read bytes into `buf`, returning how many were read. `0`
+    /// indicates the end of the input.
+    fn poll_read_into(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut [u8])
+        -> Poll<io::Result<usize>>;
+}
+
+#[cfg(feature = "tokio")]
+impl<R: tokio::io::AsyncRead + Unpin> AsyncSource for R {
+    fn poll_read_into(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut [u8])
+        -> Poll<io::Result<usize>>
+    {
+        let mut buf = tokio::io::ReadBuf::new(buf);
+        match tokio::io::AsyncRead::poll_read(self, cx, &mut buf) {
+            Poll::Ready(Ok(())) => Poll::Ready(Ok(buf.filled().len())),
+            Poll::Ready(Err(e)) => Poll::Ready(Err(e)),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+/// Adapts a `futures::io::AsyncRead` into an [`AsyncSource`].
+///
+/// ```rust
+/// use pear::input::{AsyncStream, FuturesRead};
+///
+/// let stream = AsyncStream::new(FuturesRead(&b"hello\n"[..]));
+/// assert!(stream.buffered().is_empty());
+/// ```
+#[cfg(feature = "futures-io")]
+#[derive(Debug)]
+pub struct FuturesRead<R>(pub R);
+
+#[cfg(feature = "futures-io")]
+impl<R: futures_io::AsyncRead + Unpin> AsyncSource for FuturesRead<R> {
+    fn poll_read_into(mut self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut [u8])
+        -> Poll<io::Result<usize>>
+    {
+        Pin::new(&mut self.0).poll_read(cx, buf)
+    }
+}
+
+/// Drives synchronous parsers over bytes read from an asynchronous reader: a
+/// `tokio` reader or, through [`FuturesRead`], a `futures` reader. See
+/// [`AsyncSource`].
+///
+/// Bytes are read into a [`Stream`]. When a parser reports that it needs more
+/// input, [`AsyncStream::parse()`] awaits a read from the underlying reader
+/// and retries the parser from the last committed position. Parsers are plain
+/// `#[parser]` functions over an [`AsyncInput`].
+///
+/// Because the buffer is refilled between attempts, outputs cannot borrow from
+/// the input: parsers must return owned values.
+///
+/// ```rust
+/// # #[cfg(feature = "tokio")] {
+/// use pear::input::{AsyncInput, AsyncStream, Pear};
+/// use pear::macros::parser;
+/// use pear::parsers::*;
+///
+/// type Result<'a, T> = pear::input::Result<T, AsyncInput<'a>>;
+///
+/// #[parser]
+/// fn line<'a>(input: &mut Pear<AsyncInput<'a>>) -> Result<'a, Vec<u8>> {
+///     let line = take_while(|&b| b != b'\n')?;
+///     eat(b'\n')?;
+///     line.values.to_vec()
+/// }
+///
+/// # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+/// let mut stream = AsyncStream::new(&b"hello\nworld\n"[..]);
+/// assert_eq!(stream.parse(line).await.unwrap(), Some(b"hello".to_vec()));
+/// assert_eq!(stream.parse(line).await.unwrap(), Some(b"world".to_vec()));
+/// assert_eq!(stream.parse(line).await.unwrap(), None);
+/// # });
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct AsyncStream<R> {
+    reader: R,
+    stream: Stream,
+    chunk: Vec<u8>,
+}
+
+const DEFAULT_CHUNK_SIZE: usize = 4096;
+
+impl<R: AsyncSource> AsyncStream<R> {
+    /// Creates a stream that reads from `reader` in chunks of up to 4KiB.
+    pub fn new(reader: R) -> Self {
+        AsyncStream::with_chunk_size(reader, DEFAULT_CHUNK_SIZE)
+    }
+
+    /// Creates a stream that reads from `reader` in chunks of up to `size`
+    /// bytes.
+    pub fn with_chunk_size(reader: R, size: usize) -> Self {
+        AsyncStream { reader, stream: Stream::new(), chunk: vec![0; size.max(1)] }
+    }
+
+    /// Returns the bytes that have been read but not yet committed.
+    pub fn buffered(&self) -> &[u8] {
+        self.stream.buffered()
+    }
+
+    /// Returns a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.reader
+    }
+
+    /// Returns a mutable reference to the underlying reader.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.reader
+    }
+
+    /// Runs `parser` from the last committed position, reading from the
+    /// underlying reader whenever the parser needs more input.
+    ///
+    /// Returns `Ok(None)` if the reader is exhausted and there are no buffered
+    /// bytes left to parse.
+    pub async fn parse<O, F>(&mut self, mut parser: F) -> std::result::Result<Option<O>, StreamError>
+        where F: for<'a> FnMut(&mut Pear<AsyncInput<'a>>) -> input::Result<O, AsyncInput<'a>>
+    {
+        loop {
+            if self.stream.is_complete() && self.stream.buffered().is_empty() {
+                return Ok(None);
+            }
+
+            match self.stream.parse(&mut parser) {
+                Status::Complete(value) => return Ok(Some(value)),
+                Status::Error(e) => return Err(StreamError::Parse(e.into_owned())),
+                Status::Incomplete(_) => self.fill().await?,
+            }
+        }
+    }
+
+    async fn fill(&mut self) -> io::Result<()> {
+        let (reader, chunk) = (&mut self.reader, &mut self.chunk);
+        let read = std::future::poll_fn(|cx| Pin::new(&mut *reader).poll_read_into(cx, chunk));
+        match read.await? {
+            0 => self.stream.complete(),
+            n => self.stream.feed(&self.chunk[..n]),
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,370 @@
+use std::fmt;
+
+use crate::input::{Input, Rewind, ParserInfo, Show, Length, IntoOwned};
+
+/// The order in which [`Bits`] reads the bits of each byte.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BitOrder {
+    /// The most significant bit of each byte is read first. Multi-bit values
+    /// are read most significant bit first, as in H.264 and MPEG headers.
+    MsbFirst,
+    /// The least significant bit of each byte is read first. Multi-bit values
+    /// are read least significant bit first, as in DEFLATE.
+    LsbFirst,
+}
+
+impl BitOrder {
+    #[inline(always)]
+    fn bit(self, bytes: &[u8], offset: usize) -> Option<bool> {
+        let byte = *bytes.get(offset / 8)?;
+        let shift = match self {
+            BitOrder::MsbFirst => 7 - (offset % 8),
+            BitOrder::LsbFirst => offset % 8,
+        };
+
+        Some((byte >> shift) & 1 == 1)
+    }
+}
+
+/// A position in a [`Bits`] input: a byte offset and a bit within that byte.
+///
+/// `bit` counts bits in the order they are read, so bit `0` is the most
+/// significant bit in [`BitOrder::MsbFirst`] mode and the least significant
+/// bit in [`BitOrder::LsbFirst`] mode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct BitPosition {
+    /// The offset of the byte, from the start of the input.
+    pub byte: usize,
+    /// The bit within the byte, from `0` to `7`.
+    pub bit: u8,
+}
+
+impl BitPosition {
+    fn from_offset(offset: usize) -> BitPosition {
+        BitPosition { byte: offset / 8, bit: (offset % 8) as u8 }
+    }
+
+    /// Returns the offset of `self`, in bits, from the start of the input.
+    pub fn offset(&self) -> usize {
+        self.byte * 8 + usize::from(self.bit)
+    }
+}
+
+impl fmt::Display for BitPosition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "byte {}, bit {}", self.byte, self.bit)
+    }
+}
+
+/// The context of a [`Bits`] input: the span of bits from `start` until but
+/// excluding `end`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BitSpan {
+    /// The position of the first bit in the span.
+    pub start: BitPosition,
+    /// The position just past the last bit in the span.
+    pub end: BitPosition,
+}
+
+impl IntoOwned for BitSpan {
+    type Owned = BitSpan;
+
+    #[inline(always)]
+    fn into_owned(self) -> BitSpan {
+        self
+    }
+}
+
+impl Show for BitSpan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.start == self.end {
+            write!(f, "{}", self.start)
+        } else {
+            write!(f, "{} to {}", self.start, self.end)
+        }
+    }
+}
+
+/// A run of bits in a [`Bits`] input.
+#[derive(Debug, Clone, Copy)]
+pub struct BitSlice<'a> {
+    bytes: &'a [u8],
+    order: BitOrder,
+    start: usize,
+    end: usize,
+}
+
+impl<'a> BitSlice<'a> {
+    /// Returns the `i`th bit in `self`, if there is one.
+    pub fn get(&self, i: usize) -> Option<bool> {
+        match i < self.len() {
+            true => self.order.bit(self.bytes, self.start + i),
+            false => None,
+        }
+    }
+
+    /// Returns an iterator over the bits in `self`, in the order they were
+    /// read.
+    pub fn iter(&self) -> impl Iterator<Item = bool> + 'a {
+        let (bytes, order) = (self.bytes, self.order);
+        (self.start..self.end).filter_map(move |i| order.bit(bytes, i))
+    }
+
+    /// Returns the unsigned integer the bits in `self` encode, assembled in
+    /// the input's [`BitOrder`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `self` is longer than 64 bits.
+    pub fn value(&self) -> u64 {
+        assert!(self.len() <= 64, "a bit slice of {} bits does not fit in a u64", self.len());
+        match self.order {
+            BitOrder::MsbFirst => self.iter().fold(0, |v, bit| (v << 1) | u64::from(bit)),
+            BitOrder::LsbFirst => self.iter().enumerate()
+                .fold(0, |v, (i, bit)| v | (u64::from(bit) << i)),
+        }
+    }
+
+    /// Returns the position of the first bit of `self` in the input.
+    pub fn start(&self) -> BitPosition {
+        BitPosition::from_offset(self.start)
+    }
+
+    /// Returns the position just past the last bit of `self` in the input.
+    pub fn end(&self) -> BitPosition {
+        BitPosition::from_offset(self.end)
+    }
+}
+
+impl Length for BitSlice<'_> {
+    #[inline(always)]
+    fn len(&self) -> usize {
+        self.end - self.start
+    }
+}
+
+impl Show for BitSlice<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "0b")?;
+        for bit in self.iter() {
+            write!(f, "{}", u8::from(bit))?;
+        }
+
+        Ok(())
+    }
+}
+
+impl PartialEq for BitSlice<'_> {
+    fn eq(&self, other: &BitSlice<'_>) -> bool {
+        self.len() == other.len() && self.iter().eq(other.iter())
+    }
+}
+
+impl PartialEq<[bool]> for BitSlice<'_> {
+    fn eq(&self, other: &[bool]) -> bool {
+        self.len() == other.len() && self.iter().eq(other.iter().copied())
+    }
+}
+
+impl PartialEq<&[bool]> for BitSlice<'_> {
+    fn eq(&self, other: &&[bool]) -> bool {
+        self == *other
+    }
+}
+
+impl PartialEq<BitSlice<'_>> for &[bool] {
+    fn eq(&self, other: &BitSlice<'_>) -> bool {
+        other == *self
+    }
+}
+
+macro_rules! impl_for_slice_len {
+    ($($n:expr),*) => ($(
+        impl PartialEq<BitSlice<'_>> for &[bool; $n] {
+            fn eq(&self, other: &BitSlice<'_>) -> bool {
+                other == &self[..]
+            }
+        }
+    )*)
+}
+
+impl_for_slice_len!(
+    0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16,
+    17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31, 32
+);
+
+/// A bit-level input over a byte slice for packed binary formats.
+///
+/// Tokens are single bits, as `bool`s, read in the given [`BitOrder`].
+/// Lengths, as in [`Input::slice()`], are in bits. Slices are [`BitSlice`]s
+/// and contexts are [`BitSpan`]s, which report byte and bit offsets. The
+/// [`take_bits()`], [`align_to_byte()`] and [`exp_golomb()`] parsers read
+/// common field types.
+///
+/// [`take_bits()`]: crate::parsers::take_bits()
+/// [`align_to_byte()`]: crate::parsers::align_to_byte()
+/// [`exp_golomb()`]: crate::parsers::exp_golomb()
+///
+/// ```rust
+/// use pear::input::{Pear, Bits};
+/// use pear::parsers::*;
+///
+/// let mut input = Pear::from(Bits::msb_first(&[0b1010_1100, 0xFF]));
+/// assert_eq!(take_bits(&mut input, 3).unwrap(), 0b101);
+/// assert_eq!(exp_golomb(&mut input).unwrap(), 2);
+/// assert_eq!(align_to_byte(&mut input).unwrap(), 2);
+/// assert_eq!(take_bits(&mut input, 8).unwrap(), 0xFF);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Bits<'a> {
+    bytes: &'a [u8],
+    order: BitOrder,
+    offset: usize,
+}
+
+impl<'a> Bits<'a> {
+    /// Creates an input that reads the bits of `bytes` in `order`.
+    pub fn new(bytes: &'a [u8], order: BitOrder) -> Self {
+        Bits { bytes, order, offset: 0 }
+    }
+
+    /// Creates an input that reads the bits of `bytes` most significant bit
+    /// first.
+    pub fn msb_first(bytes: &'a [u8]) -> Self {
+        Bits::new(bytes, BitOrder::MsbFirst)
+    }
+
+    /// Creates an input that reads the bits of `bytes` least significant bit
+    /// first.
+    pub fn lsb_first(bytes: &'a [u8]) -> Self {
+        Bits::new(bytes, BitOrder::LsbFirst)
+    }
+
+    /// Returns the bit order `self` reads in.
+    pub fn order(&self) -> BitOrder {
+        self.order
+    }
+
+    /// Returns the current position.
+    pub fn position(&self) -> BitPosition {
+        BitPosition::from_offset(self.offset)
+    }
+
+    /// Returns `true` if the current position is at the start of a byte.
+    pub fn is_aligned(&self) -> bool {
+        self.position().bit == 0
+    }
+
+    fn remaining(&self) -> usize {
+        self.bytes.len() * 8 - self.offset
+    }
+
+    fn bit_slice(&self, start: usize, end: usize) -> BitSlice<'a> {
+        BitSlice { bytes: self.bytes, order: self.order, start, end }
+    }
+}
+
+impl Rewind for Bits<'_> {
+    fn rewind_to(&mut self, marker: Self::Marker) {
+        self.offset = marker;
+    }
+}
+
+impl<'a> Input for Bits<'a> {
+    type Token = bool;
+    type Slice = BitSlice<'a>;
+    type Many = Self::Slice;
+
+    type Marker = usize;
+    type Context = BitSpan;
+
+    /// Returns a copy of the current token, if there is one.
+    fn token(&mut self) -> Option<Self::Token> {
+        self.order.bit(self.bytes, self.offset)
+    }
+
+    /// Returns a copy of the current slice of size `n`, if there is one.
+    fn slice(&mut self, n: usize) -> Option<Self::Slice> {
+        match n <= self.remaining() {
+            true => Some(self.bit_slice(self.offset, self.offset + n)),
+            false => None,
+        }
+    }
+
+    /// Checks if the current token fulfills `cond`.
+    fn peek<F>(&mut self, mut cond: F) -> bool
+        where F: FnMut(&Self::Token) -> bool
+    {
+        self.token().map(|t| cond(&t)).unwrap_or(false)
+    }
+
+    /// Checks if the current slice of size `n` (if any) fulfills `cond`.
+    fn peek_slice<F>(&mut self, n: usize, mut cond: F) -> bool
+        where F: FnMut(&Self::Slice) -> bool
+    {
+        self.slice(n).map(|s| cond(&s)).unwrap_or(false)
+    }
+
+    /// Checks if the current token fulfills `cond`. If so, the token is
+    /// consumed and returned. Otherwise, returns `None`.
+    fn eat<F>(&mut self, mut cond: F) -> Option<Self::Token>
+        where F: FnMut(&Self::Token) -> bool
+    {
+        let token = self.token()?;
+        if cond(&token) {
+            self.offset += 1;
+            Some(token)
+        } else {
+            None
+        }
+    }
+
+    /// Checks if the current slice of size `n` (if any) fulfills `cond`. If so,
+    /// the slice is consumed and returned. Otherwise, returns `None`.
+    fn eat_slice<F>(&mut self, n: usize, mut cond: F) -> Option<Self::Slice>
+        where F: FnMut(&Self::Slice) -> bool
+    {
+        let slice = self.slice(n)?;
+        if cond(&slice) {
+            self.offset += n;
+            Some(slice)
+        } else {
+            None
+        }
+    }
+
+    /// Takes tokens while `cond` returns true, collecting them into a
+    /// `Self::Many` and returning it.
+    fn take<F>(&mut self, mut cond: F) -> Self::Many
+        where F: FnMut(&Self::Token) -> bool
+    {
+        let start = self.offset;
+        while self.eat(&mut cond).is_some() { }
+        self.bit_slice(start, self.offset)
+    }
+
+    /// Skips tokens while `cond` returns true. Returns the number of skipped
+    /// tokens.
+    fn skip<F>(&mut self, cond: F) -> usize
+        where F: FnMut(&Self::Token) -> bool
+    {
+        self.take(cond).len()
+    }
+
+    /// Returns `true` if there are at least `n` tokens remaining.
+    fn has(&mut self, n: usize) -> bool {
+        self.remaining() >= n
+    }
+
+    #[inline(always)]
+    fn mark(&mut self, _: &ParserInfo) -> Self::Marker {
+        self.offset
+    }
+
+    fn context(&mut self, mark: Self::Marker) -> Self::Context {
+        BitSpan {
+            start: BitPosition::from_offset(mark),
+            end: BitPosition::from_offset(self.offset),
+        }
+    }
+}
@@ -0,0 +1,313 @@
+use crate::input::{Input, Rewind, ParserInfo, Show, Length, IntoOwned};
+
+/// How [`CaseFold`] folds the case of tokens and slices.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Folding {
+    /// Only ASCII letters are folded.
+    Ascii,
+    /// Characters are folded with Unicode simple case folding. Bytes are
+    /// folded as ASCII.
+    Unicode,
+}
+
+/// Trait implemented by tokens whose case can be folded.
+pub trait FoldCase {
+    /// Returns the case-folded form of `self`.
+    fn fold_case(&self, folding: Folding) -> Self;
+}
+
+impl FoldCase for u8 {
+    #[inline(always)]
+    fn fold_case(&self, _: Folding) -> Self {
+        self.to_ascii_lowercase()
+    }
+}
+
+impl FoldCase for char {
+    fn fold_case(&self, folding: Folding) -> Self {
+        if folding == Folding::Ascii || self.is_ascii() {
+            return self.to_ascii_lowercase();
+        }
+
+        // Simple case folding agrees with single-character lowercasing except
+        // for these characters, which lowercase to themselves.
+        match *self {
+            'µ' => 'μ',
+            'ſ' => 's',
+            'ς' => 'σ',
+            'ϐ' => 'β',
+            'ϑ' => 'θ',
+            'ϕ' => 'φ',
+            'ϖ' => 'π',
+            'ϰ' => 'κ',
+            'ϱ' => 'ρ',
+            'ϵ' => 'ε',
+            'ẛ' => 'ṡ',
+            '\u{1FBE}' => '\u{03B9}',
+            c => {
+                let mut lower = c.to_lowercase();
+                match (lower.next(), lower.next()) {
+                    (Some(lower), None) => lower,
+                    _ => c,
+                }
+            }
+        }
+    }
+}
+
+/// Returns `true` if `a` and `b` are equal after folding their case. In
+/// [`Folding::Unicode`] mode, the bytes are compared as UTF-8 text.
+fn eq_folded(a: &[u8], b: &[u8], folding: Folding) -> bool {
+    match folding {
+        Folding::Ascii => a.eq_ignore_ascii_case(b),
+        Folding::Unicode => {
+            let (a, b) = (String::from_utf8_lossy(a), String::from_utf8_lossy(b));
+            let fold = |c: char| c.fold_case(folding);
+            a.chars().map(fold).eq(b.chars().map(fold))
+        }
+    }
+}
+
+/// A slice of a [`CaseFold`] input.
+///
+/// Dereferences to the slice as it appears in the input, in its original case.
+/// Compares equal to `&str` and `&[u8]` values that match it ignoring case.
+#[derive(Debug, Clone)]
+pub struct Caseless<S> {
+    value: S,
+    folding: Folding,
+}
+
+impl<S> Caseless<S> {
+    /// Returns the slice as it appears in the input.
+    pub fn into_inner(self) -> S {
+        self.value
+    }
+}
+
+impl<S> std::ops::Deref for Caseless<S> {
+    type Target = S;
+
+    fn deref(&self) -> &Self::Target {
+        &self.value
+    }
+}
+
+/// Slices folded differently are compared under the stricter ASCII folding.
+impl<S: AsRef<[u8]>> PartialEq for Caseless<S> {
+    fn eq(&self, other: &Caseless<S>) -> bool {
+        let folding = match self.folding == other.folding {
+            true => self.folding,
+            false => Folding::Ascii,
+        };
+
+        eq_folded(self.value.as_ref(), other.value.as_ref(), folding)
+    }
+}
+
+impl<S: AsRef<[u8]>> PartialEq<Caseless<S>> for &str {
+    fn eq(&self, other: &Caseless<S>) -> bool {
+        eq_folded(self.as_bytes(), other.value.as_ref(), other.folding)
+    }
+}
+
+impl<S: AsRef<[u8]>> PartialEq<Caseless<S>> for &[u8] {
+    fn eq(&self, other: &Caseless<S>) -> bool {
+        eq_folded(self, other.value.as_ref(), other.folding)
+    }
+}
+
+impl<S: Length> Length for Caseless<S> {
+    #[inline(always)]
+    fn len(&self) -> usize {
+        self.value.len()
+    }
+}
+
+impl<S: IntoOwned> IntoOwned for Caseless<S> {
+    type Owned = Caseless<S::Owned>;
+
+    #[inline(always)]
+    fn into_owned(self) -> Self::Owned {
+        Caseless { value: self.value.into_owned(), folding: self.folding }
+    }
+}
+
+impl<S: Show> Show for Caseless<S> {
+    #[inline(always)]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Show::fmt(&self.value, f)
+    }
+
+    #[inline(always)]
+    fn literal(&self) -> Option<String> {
+        Show::literal(&self.value)
+    }
+}
+
+/// An input wrapper that matches tokens and slices ignoring case.
+///
+/// Tokens are case-folded: `token()`, `eat()` and friends produce folded
+/// tokens, and conditions see folded tokens, so `eat(input, 's')` matches both
+/// `s` and `S`. Slices are [`Caseless`] and retain their original case, as do
+/// the results of `take()`. Because slices are matched by length, characters
+/// whose folded form has a different encoded length only match themselves.
+///
+/// ```rust
+/// use pear::input::{Pear, Text, CaseFold};
+/// use pear::parsers::*;
+///
+/// let mut input = Pear::from(CaseFold::ascii(Text::from("SELECT *")));
+/// let keyword = eat_slice(&mut input, "select").unwrap();
+/// assert_eq!(*keyword, "SELECT");
+/// assert_eq!(eat(&mut input, ' ').unwrap(), ' ');
+/// ```
+#[derive(Debug)]
+pub struct CaseFold<I> {
+    input: I,
+    folding: Folding,
+}
+
+impl<I: Input> CaseFold<I>
+    where I::Token: FoldCase
+{
+    /// Wraps `input`, folding case as directed by `folding`.
+    pub fn new(input: I, folding: Folding) -> Self {
+        CaseFold { input, folding }
+    }
+
+    /// Wraps `input`, folding ASCII case.
+    pub fn ascii(input: I) -> Self {
+        CaseFold::new(input, Folding::Ascii)
+    }
+
+    /// Wraps `input`, folding case with Unicode simple case folding.
+    pub fn unicode(input: I) -> Self {
+        CaseFold::new(input, Folding::Unicode)
+    }
+
+    /// Returns the wrapped input.
+    pub fn into_inner(self) -> I {
+        self.input
+    }
+
+    fn caseless(&self, value: I::Slice) -> Caseless<I::Slice> {
+        Caseless { value, folding: self.folding }
+    }
+}
+
+impl<I: Input> Input for CaseFold<I>
+    where I::Token: FoldCase, I::Slice: AsRef<[u8]>
+{
+    type Token = I::Token;
+    type Slice = Caseless<I::Slice>;
+    type Many = I::Many;
+
+    type Marker = I::Marker;
+    type Context = I::Context;
+
+    /// Returns a copy of the current token, if there is one.
+    fn token(&mut self) -> Option<Self::Token> {
+        self.input.token().map(|t| t.fold_case(self.folding))
+    }
+
+    /// Returns a copy of the current slice of size `n`, if there is one.
+    fn slice(&mut self, n: usize) -> Option<Self::Slice> {
+        self.input.slice(n).map(|s| self.caseless(s))
+    }
+
+    /// Checks if the current token fulfills `cond`.
+    fn peek<F>(&mut self, mut cond: F) -> bool
+        where F: FnMut(&Self::Token) -> bool
+    {
+        let folding = self.folding;
+        self.input.peek(|t| cond(&t.fold_case(folding)))
+    }
+
+    /// Checks if the current slice of size `n` (if any) fulfills `cond`.
+    fn peek_slice<F>(&mut self, n: usize, mut cond: F) -> bool
+        where F: FnMut(&Self::Slice) -> bool
+    {
+        self.slice(n).map(|s| cond(&s)).unwrap_or(false)
+    }
+
+    /// Checks if the current token fulfills `cond`. If so, the token is
+    /// consumed and returned. Otherwise, returns `None`.
+    fn eat<F>(&mut self, mut cond: F) -> Option<Self::Token>
+        where F: FnMut(&Self::Token) -> bool
+    {
+        let folding = self.folding;
+        self.input.eat(|t| cond(&t.fold_case(folding))).map(|t| t.fold_case(folding))
+    }
+
+    /// Checks if the current slice of size `n` (if any) fulfills `cond`. If so,
+    /// the slice is consumed and returned. Otherwise, returns `None`.
+    fn eat_slice<F>(&mut self, n: usize, mut cond: F) -> Option<Self::Slice>
+        where F: FnMut(&Self::Slice) -> bool
+    {
+        let slice = self.slice(n)?;
+        if cond(&slice) {
+            self.input.eat_slice(n, |_| true)?;
+            Some(slice)
+        } else {
+            None
+        }
+    }
+
+    /// Takes tokens while `cond` returns true, collecting them into a
+    /// `Self::Many` and returning it.
+    fn take<F>(&mut self, mut cond: F) -> Self::Many
+        where F: FnMut(&Self::Token) -> bool
+    {
+        let folding = self.folding;
+        self.input.take(|t| cond(&t.fold_case(folding)))
+    }
+
+    /// Skips tokens while `cond` returns true. Returns the number of skipped
+    /// tokens.
+    fn skip<F>(&mut self, mut cond: F) -> usize
+        where F: FnMut(&Self::Token) -> bool
+    {
+        let folding = self.folding;
+        self.input.skip(|t| cond(&t.fold_case(folding)))
+    }
+
+    /// Returns `true` if there are at least `n` tokens remaining.
+    #[inline(always)]
+    fn has(&mut self, n: usize) -> bool {
+        self.input.has(n)
+    }
+
+    #[inline(always)]
+    fn mark(&mut self, info: &ParserInfo) -> Self::Marker {
+        self.input.mark(info)
+    }
+
+    #[inline(always)]
+    fn context(&mut self, mark: Self::Marker) -> Self::Context {
+        self.input.context(mark)
+    }
+
+    #[inline(always)]
+    fn is_partial(&mut self) -> bool {
+        self.input.is_partial()
+    }
+
+    #[inline(always)]
+    fn ignores_case(&mut self) -> bool {
+        true
+    }
+
+    #[inline(always)]
+    fn malformed(&mut self) -> Option<(&'static str, usize)> {
+        self.input.malformed()
+    }
+}
+
+impl<I: Input + Rewind> Rewind for CaseFold<I>
+    where I::Token: FoldCase, I::Slice: AsRef<[u8]>
+{
+    fn rewind_to(&mut self, marker: Self::Marker) {
+        self.input.rewind_to(marker)
+    }
+}
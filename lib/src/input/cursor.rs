@@ -1,6 +1,7 @@
 use std::fmt::Debug;
+use std::borrow::Cow;
 
-use crate::input::{Input, Show, Rewind, ParserInfo, Length};
+use crate::input::{Input, Show, Rewind, ParserInfo, Length, IntoOwned};
 
 #[derive(Debug)]
 pub struct Cursor<T> {
@@ -15,6 +16,7 @@ impl<T: Copy> From<T> for Cursor<T> {
 }
 
 #[derive(Debug, PartialEq, Eq, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Extent<T> {
     pub start: usize,
     pub end: usize,
@@ -35,6 +37,12 @@ impl<T> std::ops::Deref for Extent<T> {
     }
 }
 
+impl<T: AsRef<U>, U: ?Sized> AsRef<U> for Extent<T> {
+    fn as_ref(&self) -> &U {
+        self.values.as_ref()
+    }
+}
+
 impl<T: PartialEq> PartialEq<T> for Extent<T> {
     fn eq(&self, other: &T) -> bool {
         &self.values == other
@@ -53,6 +61,18 @@ impl<T: PartialEq> PartialEq<Extent<&[T]>> for &[T] {
     }
 }
 
+impl<T: PartialEq + Clone> PartialEq<Extent<Cow<'_, [T]>>> for &[T] {
+    fn eq(&self, other: &Extent<Cow<'_, [T]>>) -> bool {
+        &other.values[..] == *self
+    }
+}
+
+impl<T: PartialEq + Clone> PartialEq<&[T]> for Extent<Cow<'_, [T]>> {
+    fn eq(&self, other: &&[T]) -> bool {
+        &self.values[..] == *other
+    }
+}
+
 macro_rules! impl_for_slice_len {
     ($($n:expr),*) => ($(
         impl<T: PartialEq> PartialEq<Extent<&[T]>> for &[T; $n] {
@@ -60,6 +80,12 @@ macro_rules! impl_for_slice_len {
                 &other.values[..] == *self
             }
         }
+
+        impl<T: PartialEq + Clone> PartialEq<Extent<Cow<'_, [T]>>> for &[T; $n] {
+            fn eq(&self, other: &Extent<Cow<'_, [T]>>) -> bool {
+                &other.values[..] == *self
+            }
+        }
     )*)
 }
 
@@ -90,6 +116,19 @@ impl<T: ?Sized + ToOwned> Extent<&T> {
     }
 }
 
+impl<T: IntoOwned> IntoOwned for Extent<T> {
+    type Owned = Extent<T::Owned>;
+
+    #[inline(always)]
+    fn into_owned(self) -> Self::Owned {
+        Extent {
+            start: self.start,
+            end: self.end,
+            values: self.values.into_owned(),
+        }
+    }
+}
+
 pub trait Indexable: Sized {
     type One: Clone;
     type Iter: Iterator<Item = Self::One>;
@@ -0,0 +1,355 @@
+use std::borrow::Cow;
+use std::fmt::{self, Debug};
+use std::ops::{Add, Index, Range};
+
+use crate::input::{Input, Rewind, ParserInfo, Show, Length, Cursor, Extent, IntoOwned};
+
+/// A slice that is either a range of indices into a source or a concrete
+/// value.
+///
+/// [`IndexedInput`] produces `Indexed::Indexed` slices: plain offset ranges
+/// that don't borrow from the input. Parsed structures can store them and
+/// resolve them against the source later with [`Indexed::to_source()`].
+/// `Indexed::Concrete` values hold data that did not come from the source.
+#[derive(PartialEq)]
+pub enum Indexed<'a, T: ?Sized + ToOwned> {
+    /// The range `start..end` of the source.
+    Indexed(usize, usize),
+    /// A concrete value.
+    Concrete(Cow<'a, T>)
+}
+
+impl<'a, T: ?Sized + ToOwned + 'a, C: Into<Cow<'a, T>>> From<C> for Indexed<'a, T> {
+    #[inline(always)]
+    fn from(value: C) -> Indexed<'a, T> {
+        Indexed::Concrete(value.into())
+    }
+}
+
+impl<'a, T: ?Sized + ToOwned + 'a> Indexed<'a, T> {
+    /// Returns a concrete `Indexed` holding `cow`.
+    #[inline(always)]
+    pub fn from_cow(cow: Cow<'a, T>) -> Indexed<'a, T> {
+        Indexed::Concrete(cow)
+    }
+
+    /// Converts an indexed `Indexed<T>` into an indexed `Indexed<U>` with the
+    /// same range.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `self` is concrete.
+    ///
+    /// # Safety
+    ///
+    /// The range must be valid for the source as a `U`. For instance, when
+    /// coercing `[u8]` into `str`, the range must lie on `char` boundaries of
+    /// a source that is valid UTF-8.
+    #[inline(always)]
+    pub unsafe fn coerce<U: ?Sized + ToOwned>(self) -> Indexed<'a, U> {
+        match self {
+            Indexed::Indexed(a, b) => Indexed::Indexed(a, b),
+            _ => panic!("cannot convert indexed T to U unless indexed")
+        }
+    }
+
+    /// Whether `self` is a range of indices into a source.
+    pub fn is_indexed(&self) -> bool {
+        match *self {
+            Indexed::Indexed(..) => true,
+            Indexed::Concrete(..) => false,
+        }
+    }
+
+    /// Returns a copy of `self` that doesn't borrow, cloning a borrowed
+    /// concrete value.
+    pub fn into_owned(self) -> Indexed<'static, T> {
+        match self {
+            Indexed::Indexed(a, b) => Indexed::Indexed(a, b),
+            Indexed::Concrete(cow) => Indexed::Concrete(Cow::Owned(cow.into_owned())),
+        }
+    }
+}
+
+impl<T: ?Sized + ToOwned + 'static> IntoOwned for Indexed<'_, T> {
+    type Owned = Indexed<'static, T>;
+
+    #[inline(always)]
+    fn into_owned(self) -> Self::Owned {
+        Indexed::into_owned(self)
+    }
+}
+
+impl<'a, T: ?Sized + ToOwned + 'a> Indexed<'a, T>
+    where T: Length + Index<Range<usize>, Output = T>
+{
+    /// Whether `self` is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Retrieves the value `self` corresponds to. If `self` is indexed, the
+    /// corresponding subslice of `source` is returned. Otherwise, the concrete
+    /// value is returned.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `self` is indexed and `source` is `None`.
+    pub fn to_source<'s>(&'s self, source: Option<&'s T>) -> &'s T {
+        match *self {
+            Indexed::Indexed(i, j) => match source {
+                Some(source) => &source[i..j],
+                None => panic!("cannot convert indexed T to T without a source"),
+            },
+            Indexed::Concrete(ref cow) => cow.as_ref(),
+        }
+    }
+
+    /// Converts `self` into a concrete value, copying the corresponding
+    /// subslice of `source` if `self` is indexed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `self` is indexed and `source` is `None`.
+    pub fn into_concrete(self, source: Option<&T>) -> Cow<'a, T> {
+        match self {
+            Indexed::Indexed(..) => Cow::Owned(self.to_source(source).to_owned()),
+            Indexed::Concrete(cow) => cow,
+        }
+    }
+}
+
+impl<'a, T: ?Sized + ToOwned + AsRef<[u8]> + 'a> Indexed<'a, T> {
+    /// Returns the range `needle` occupies in `haystack` or `None` if `needle`
+    /// is not a subslice of `haystack`.
+    pub fn checked_from(needle: &T, haystack: &T) -> Option<Indexed<'a, T>> {
+        let (needle, haystack) = (needle.as_ref(), haystack.as_ref());
+        let haystack_start = haystack.as_ptr() as usize;
+        let needle_start = needle.as_ptr() as usize;
+
+        if needle_start < haystack_start {
+            return None;
+        }
+
+        if (needle_start + needle.len()) > (haystack_start + haystack.len()) {
+            return None;
+        }
+
+        let start = needle_start - haystack_start;
+        Some(Indexed::Indexed(start, start + needle.len()))
+    }
+
+    /// Returns the range `needle` occupies in `haystack`.
+    ///
+    /// # Safety
+    ///
+    /// `needle` must be a subslice of `haystack`.
+    pub unsafe fn unchecked_from(needle: &T, haystack: &T) -> Indexed<'a, T> {
+        let (needle, haystack) = (needle.as_ref(), haystack.as_ref());
+        let start = needle.as_ptr() as usize - haystack.as_ptr() as usize;
+        Indexed::Indexed(start, start + needle.len())
+    }
+}
+
+/// Merges two adjacent ranges: `Indexed(a, b) + Indexed(b, c)` is
+/// `Indexed(a, c)`.
+///
+/// # Panics
+///
+/// Panics if either value is concrete or if the ranges are not adjacent.
+impl<'a, T: ?Sized + ToOwned + 'a> Add for Indexed<'a, T> {
+    type Output = Indexed<'a, T>;
+
+    fn add(self, other: Indexed<'a, T>) -> Indexed<'a, T> {
+        match (self, other) {
+            (Indexed::Indexed(a, b), Indexed::Indexed(c, d)) if b == c && a <= d => {
+                Indexed::Indexed(a, d)
+            }
+            _ => panic!("+ requires adjacent indexed ranges")
+        }
+    }
+}
+
+impl<'a, T: ToOwned + ?Sized + 'a> Clone for Indexed<'a, T> {
+    fn clone(&self) -> Self {
+        match *self {
+            Indexed::Indexed(a, b) => Indexed::Indexed(a, b),
+            Indexed::Concrete(ref cow) => Indexed::Concrete(cow.clone())
+        }
+    }
+}
+
+impl<'a, T: ?Sized + 'a> Debug for Indexed<'a, T>
+    where T: ToOwned + Debug, T::Owned: Debug
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Indexed::Indexed(a, b) => fmt::Debug::fmt(&(a, b), f),
+            Indexed::Concrete(ref cow) => fmt::Debug::fmt(cow, f),
+        }
+    }
+}
+
+impl<'a, T: ?Sized + Length + ToOwned + 'a> Length for Indexed<'a, T> {
+    #[inline(always)]
+    fn len(&self) -> usize {
+        match *self {
+            Indexed::Indexed(a, b) => b - a,
+            Indexed::Concrete(ref cow) => cow.len()
+        }
+    }
+}
+
+/// An input over a `str` or `[u8]` source that produces [`Indexed`] slices.
+///
+/// Slices are returned as `Indexed::Indexed` ranges. Conditions passed to
+/// `peek_slice()` and `eat_slice()` see a concrete slice so that they can
+/// inspect its contents.
+///
+/// ```rust
+/// use pear::input::{Pear, Indexed, IndexedInput};
+/// use pear::parsers::*;
+///
+/// let source = "key=value";
+/// let mut input: Pear<IndexedInput<'_, str>> = Pear::new(source);
+/// let key = take_while(&mut input, |&c| c != '=').unwrap();
+/// let eq = eat_slice(&mut input, "=").unwrap();
+/// assert_eq!(key.clone() + eq, Indexed::Indexed(0, 4));
+/// assert_eq!(key.to_source(Some(source)), "key");
+/// ```
+#[derive(Debug)]
+pub struct IndexedInput<'a, T: ?Sized> {
+    cursor: Cursor<&'a T>,
+}
+
+impl<'a, T: ?Sized + 'a> IndexedInput<'a, T> {
+    /// Returns the source being parsed.
+    pub fn source(&self) -> &'a T {
+        self.cursor.start
+    }
+
+    /// Returns the source being parsed as a `Cow`.
+    #[inline(always)]
+    pub fn cow_source(&self) -> Cow<'a, T> where T: ToOwned {
+        Cow::Borrowed(self.cursor.start)
+    }
+}
+
+macro_rules! impl_indexed_input {
+    ($T:ty, token = $token:ty) => (
+        impl<'a> From<&'a $T> for IndexedInput<'a, $T> {
+            #[inline(always)]
+            fn from(source: &'a $T) -> Self {
+                IndexedInput { cursor: Cursor::from(source) }
+            }
+        }
+
+        impl Show for Indexed<'_, $T> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                match *self {
+                    Indexed::Indexed(a, b) => write!(f, "{}..{}", a, b),
+                    Indexed::Concrete(ref cow) => Show::fmt(&cow.as_ref(), f),
+                }
+            }
+        }
+
+        impl<'a> PartialEq<Indexed<'a, $T>> for &$T {
+            fn eq(&self, other: &Indexed<'a, $T>) -> bool {
+                match other {
+                    Indexed::Concrete(cow) => *self == cow.as_ref(),
+                    Indexed::Indexed(..) => false,
+                }
+            }
+        }
+
+        impl<'a> Input for IndexedInput<'a, $T> {
+            type Token = $token;
+            type Slice = Indexed<'a, $T>;
+            type Many = Indexed<'a, $T>;
+
+            type Marker = usize;
+            type Context = Extent<&'a $T>;
+
+            /// Returns a copy of the current token, if there is one.
+            fn token(&mut self) -> Option<Self::Token> {
+                self.cursor.token()
+            }
+
+            /// Returns a copy of the current slice of size `n`, if there is one.
+            fn slice(&mut self, n: usize) -> Option<Self::Slice> {
+                self.cursor.slice(n).map(|e| Indexed::Indexed(e.start, e.end))
+            }
+
+            /// Checks if the current token fulfills `cond`.
+            fn peek<F>(&mut self, cond: F) -> bool
+                where F: FnMut(&Self::Token) -> bool
+            {
+                self.cursor.peek(cond)
+            }
+
+            /// Checks if the current slice of size `n` (if any) fulfills `cond`.
+            fn peek_slice<F>(&mut self, n: usize, mut cond: F) -> bool
+                where F: FnMut(&Self::Slice) -> bool
+            {
+                self.cursor.peek_slice(n, |e| cond(&Indexed::from(e.values)))
+            }
+
+            /// Checks if the current token fulfills `cond`. If so, the token is
+            /// consumed and returned. Otherwise, returns `None`.
+            fn eat<F>(&mut self, cond: F) -> Option<Self::Token>
+                where F: FnMut(&Self::Token) -> bool
+            {
+                self.cursor.eat(cond)
+            }
+
+            /// Checks if the current slice of size `n` (if any) fulfills `cond`. If so,
+            /// the slice is consumed and returned. Otherwise, returns `None`.
+            fn eat_slice<F>(&mut self, n: usize, mut cond: F) -> Option<Self::Slice>
+                where F: FnMut(&Self::Slice) -> bool
+            {
+                self.cursor.eat_slice(n, |e| cond(&Indexed::from(e.values)))
+                    .map(|e| Indexed::Indexed(e.start, e.end))
+            }
+
+            /// Takes tokens while `cond` returns true, collecting them into a
+            /// `Self::Many` and returning it.
+            fn take<F>(&mut self, cond: F) -> Self::Many
+                where F: FnMut(&Self::Token) -> bool
+            {
+                let extent = self.cursor.take(cond);
+                Indexed::Indexed(extent.start, extent.end)
+            }
+
+            /// Skips tokens while `cond` returns true. Returns the number of skipped
+            /// tokens.
+            fn skip<F>(&mut self, cond: F) -> usize
+                where F: FnMut(&Self::Token) -> bool
+            {
+                self.cursor.skip(cond)
+            }
+
+            /// Returns `true` if there are at least `n` tokens remaining.
+            fn has(&mut self, n: usize) -> bool {
+                self.cursor.has(n)
+            }
+
+            #[inline(always)]
+            fn mark(&mut self, info: &ParserInfo) -> Self::Marker {
+                self.cursor.mark(info)
+            }
+
+            fn context(&mut self, mark: Self::Marker) -> Self::Context {
+                self.cursor.context(mark)
+            }
+        }
+
+        impl<'a> Rewind for IndexedInput<'a, $T> {
+            fn rewind_to(&mut self, marker: Self::Marker) {
+                self.cursor.rewind_to(marker)
+            }
+        }
+    )
+}
+
+impl_indexed_input!([u8], token = u8);
+impl_indexed_input!(str, token = char);
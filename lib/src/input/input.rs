@@ -9,6 +9,7 @@ impl<I: Input, T> Token<I> for T where T: Show + PartialEq<I::Token> { }
 impl<I: Input, S> Slice<I> for S where S: Show + Length + PartialEq<I::Slice> { }
 
 #[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct ParserInfo {
     pub name: &'static str,
     pub raw: bool,
@@ -71,4 +72,34 @@ pub trait Input: Sized {
     /// Returns a context to identify the input spanning from `mark` until but
     /// excluding the current position.
     fn context(&mut self, _mark: Self::Marker) -> Self::Context;
+
+    /// Returns `true` if more tokens may follow the ones currently available.
+    ///
+    /// Parsers that run out of tokens on a partial input fail with
+    /// [`Expected::Incomplete`](crate::error::Expected::Incomplete) instead of
+    /// a syntax error. By default, inputs are complete and this returns
+    /// `false`.
+    fn is_partial(&mut self) -> bool {
+        false
+    }
+
+    /// Returns `true` if the input matches slices ignoring case.
+    ///
+    /// Parsers that fail to match a slice on such an input note that the
+    /// expected slice is case-insensitive. By default, returns `false`.
+    fn ignores_case(&mut self) -> bool {
+        false
+    }
+
+    /// Returns the name of the encoding and the byte offset of malformed data
+    /// at the current position, if there is any.
+    ///
+    /// Inputs that decode tokens from raw bytes stop producing tokens at
+    /// malformed data. Parsers that fail there report
+    /// [`Expected::Malformed`](crate::error::Expected::Malformed) instead of a
+    /// syntax error. By default, inputs are never malformed and this returns
+    /// `None`.
+    fn malformed(&mut self) -> Option<(&'static str, usize)> {
+        None
+    }
 }
@@ -72,3 +72,10 @@ impl Length for String {
         String::len(self)
     }
 }
+
+impl Length for std::borrow::Cow<'_, str> {
+    #[inline(always)]
+    fn len(&self) -> usize {
+        str::len(self)
+    }
+}
@@ -6,16 +6,52 @@ mod text;
 mod text_file;
 mod show;
 mod pear;
+mod partial;
+mod stream;
+mod segments;
+mod utf8;
+mod transcode;
+mod case;
+mod arc_text;
+mod indexed;
+mod bits;
+mod owned;
+mod suggest;
+#[cfg(any(feature = "tokio", feature = "futures-io"))] mod async_stream;
+#[cfg(feature = "ropey")] mod rope;
 
-pub use self::pear::{Pear, Debugger, Options};
+pub use self::pear::{Pear, Debugger, Options, StateCheckpoint, Limit, StackPolicy};
+#[doc(hidden)] pub use self::pear::grow_stack;
 pub use input::{Input, Rewind, Token, Slice, ParserInfo};
 pub use cursor::{Cursor, Extent};
-pub use text::{Text, Span};
+pub use text::{Text, Span, OwnedSpan};
 pub use length::Length;
 pub use show::Show;
+pub use owned::IntoOwned;
+pub use partial::Partial;
+pub use stream::{Stream, Status};
+pub use segments::Segments;
+pub use utf8::Utf8Bytes;
+pub use transcode::{Transcoded, Encoding};
+pub use case::{CaseFold, Caseless, Folding, FoldCase};
+pub use arc_text::{ArcText, ArcStr};
+pub use indexed::{Indexed, IndexedInput};
+pub use bits::{Bits, BitOrder, BitSlice, BitSpan, BitPosition};
+#[cfg(any(feature = "tokio", feature = "futures-io"))]
+pub use async_stream::{AsyncStream, AsyncSource, AsyncInput, StreamError, OwnedParseError};
+#[cfg(feature = "futures-io")]
+pub use async_stream::FuturesRead;
+#[cfg(feature = "ropey")]
+pub use rope::RopeInput;
 
 use crate::error;
 
 pub type Expected<I> = error::Expected<<I as Input>::Token, <I as Input>::Slice>;
-pub type ParseError<I> = error::ParseError<<I as Input>::Context, Expected<I>>;
-pub type Result<T, I> = std::result::Result<T, ParseError<I>>;
+pub type ParseError<I, E = Expected<I>> = error::ParseError<<I as Input>::Context, E>;
+
+/// The result of a parser over `I` failing with an `E`.
+///
+/// A `#[parser]` whose error type `E` isn't the default [`Expected<I>`]
+/// requires `E: From<Expected<I>>`: it fails with an `Expected` converted into
+/// `E` when a limit in its [`Options`] is exceeded.
+pub type Result<T, I, E = Expected<I>> = std::result::Result<T, error::Failure<ParseError<I, E>>>;
@@ -0,0 +1,79 @@
+use std::borrow::Cow;
+
+/// Conversion of a value that may borrow from the input into one that doesn't.
+///
+/// Implemented by the tokens, slices, and contexts of the built-in inputs,
+/// and by [`Expected`](crate::error::Expected) when its token and slice are,
+/// so that a [`ParseError`](crate::error::ParseError) can outlive its input
+/// via [`ParseError::into_owned()`](crate::error::ParseError::into_owned()).
+pub trait IntoOwned {
+    /// The owned, `'static` version of `Self`.
+    type Owned: 'static;
+
+    /// Converts `self` into its owned version, cloning any borrowed data.
+    fn into_owned(self) -> Self::Owned;
+}
+
+macro_rules! impl_into_owned_for_static {
+    ($($T:ty),* $(,)?) => ($(
+        impl IntoOwned for $T {
+            type Owned = $T;
+
+            #[inline(always)]
+            fn into_owned(self) -> Self::Owned {
+                self
+            }
+        }
+    )*)
+}
+
+impl_into_owned_for_static! {
+    (), bool, char, String,
+    u8, u16, u32, u64, u128, usize,
+    i8, i16, i32, i64, i128, isize,
+}
+
+impl IntoOwned for &str {
+    type Owned = String;
+
+    #[inline(always)]
+    fn into_owned(self) -> Self::Owned {
+        self.to_string()
+    }
+}
+
+impl<T: Clone + 'static> IntoOwned for &[T] {
+    type Owned = Vec<T>;
+
+    #[inline(always)]
+    fn into_owned(self) -> Self::Owned {
+        self.to_vec()
+    }
+}
+
+impl<T: 'static> IntoOwned for Vec<T> {
+    type Owned = Vec<T>;
+
+    #[inline(always)]
+    fn into_owned(self) -> Self::Owned {
+        self
+    }
+}
+
+impl<T: ?Sized + ToOwned + 'static> IntoOwned for Cow<'_, T> {
+    type Owned = Cow<'static, T>;
+
+    #[inline(always)]
+    fn into_owned(self) -> Cow<'static, T> {
+        Cow::Owned(Cow::into_owned(self))
+    }
+}
+
+impl<T: IntoOwned> IntoOwned for Option<T> {
+    type Owned = Option<T::Owned>;
+
+    #[inline(always)]
+    fn into_owned(self) -> Self::Owned {
+        self.map(|v| v.into_owned())
+    }
+}
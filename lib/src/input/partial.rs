@@ -0,0 +1,217 @@
+use crate::input::{Input, Rewind, ParserInfo};
+
+/// An input that may be a prefix of the full input.
+///
+/// `Partial` wraps another input and marks it as partial until
+/// [`Partial::complete()`] is called. While partial, parsers that run out of
+/// tokens fail with [`Expected::Incomplete`](crate::error::Expected::Incomplete)
+/// instead of a syntax error.
+///
+/// Additionally, `Partial` remembers whether _any_ operation ran out of tokens,
+/// even one whose failure was later recovered from, such as a `switch!` arm or
+/// an `ok()` combinator. Such a parse is said to be _starved_: its outcome
+/// might have been different had more input been available.
+#[derive(Debug)]
+pub struct Partial<I> {
+    input: I,
+    complete: bool,
+    starved: bool,
+    needed: Option<usize>,
+}
+
+impl<I: Input> Partial<I> {
+    /// Wraps `input`, marking it as partial.
+    pub fn new(input: I) -> Self {
+        Partial { input, complete: false, starved: false, needed: None }
+    }
+
+    /// Marks the input as complete: no more tokens will follow the ones that
+    /// are currently available.
+    pub fn complete(&mut self) {
+        self.complete = true;
+    }
+
+    /// Returns `true` if an operation ran out of tokens while the input was
+    /// partial.
+    pub fn is_starved(&self) -> bool {
+        self.starved
+    }
+
+    /// Returns the number of additional tokens that the starving operations
+    /// required, if known.
+    pub fn needed(&self) -> Option<usize> {
+        self.needed
+    }
+
+    /// Returns the wrapped input.
+    pub fn into_inner(self) -> I {
+        self.input
+    }
+
+    fn starve(&mut self, needed: Option<usize>) {
+        if self.complete {
+            return;
+        }
+
+        self.needed = match (self.starved, self.needed, needed) {
+            (false, _, needed) => needed,
+            (true, Some(a), Some(b)) => Some(a.max(b)),
+            (true, _, _) => None,
+        };
+
+        self.starved = true;
+    }
+
+    fn starve_for(&mut self, n: usize) {
+        let available = (0..n).rev().find(|&k| self.input.has(k)).unwrap_or(0);
+        self.starve(Some(n - available));
+    }
+}
+
+impl<I: Input> From<I> for Partial<I> {
+    fn from(input: I) -> Self {
+        Partial::new(input)
+    }
+}
+
+impl<I: Input> Input for Partial<I> {
+    type Token = I::Token;
+    type Slice = I::Slice;
+    type Many = I::Many;
+
+    type Marker = I::Marker;
+    type Context = I::Context;
+
+    /// Returns a copy of the current token, if there is one.
+    fn token(&mut self) -> Option<Self::Token> {
+        if !self.input.has(1) {
+            self.starve(Some(1));
+        }
+
+        self.input.token()
+    }
+
+    /// Returns a copy of the current slice of size `n`, if there is one.
+    fn slice(&mut self, n: usize) -> Option<Self::Slice> {
+        if !self.input.has(n) {
+            self.starve_for(n);
+        }
+
+        self.input.slice(n)
+    }
+
+    /// Checks if the current token fulfills `cond`.
+    fn peek<F>(&mut self, cond: F) -> bool
+        where F: FnMut(&Self::Token) -> bool
+    {
+        if !self.input.has(1) {
+            self.starve(Some(1));
+        }
+
+        self.input.peek(cond)
+    }
+
+    /// Checks if the current slice of size `n` (if any) fulfills `cond`.
+    fn peek_slice<F>(&mut self, n: usize, cond: F) -> bool
+        where F: FnMut(&Self::Slice) -> bool
+    {
+        if !self.input.has(n) {
+            self.starve_for(n);
+        }
+
+        self.input.peek_slice(n, cond)
+    }
+
+    /// Checks if the current token fulfills `cond`. If so, the token is
+    /// consumed and returned. Otherwise, returns `None`.
+    fn eat<F>(&mut self, cond: F) -> Option<Self::Token>
+        where F: FnMut(&Self::Token) -> bool
+    {
+        if !self.input.has(1) {
+            self.starve(Some(1));
+        }
+
+        self.input.eat(cond)
+    }
+
+    /// Checks if the current slice of size `n` (if any) fulfills `cond`. If so,
+    /// the slice is consumed and returned. Otherwise, returns `None`.
+    fn eat_slice<F>(&mut self, n: usize, cond: F) -> Option<Self::Slice>
+        where F: FnMut(&Self::Slice) -> bool
+    {
+        if !self.input.has(n) {
+            self.starve_for(n);
+        }
+
+        self.input.eat_slice(n, cond)
+    }
+
+    /// Takes tokens while `cond` returns true, collecting them into a
+    /// `Self::Many` and returning it. If every available token is taken, the
+    /// input starves: more tokens may have matched.
+    fn take<F>(&mut self, cond: F) -> Self::Many
+        where F: FnMut(&Self::Token) -> bool
+    {
+        let many = self.input.take(cond);
+        if !self.input.has(1) {
+            self.starve(None);
+        }
+
+        many
+    }
+
+    /// Skips tokens while `cond` returns true. Returns the number of skipped
+    /// tokens. If every available token is skipped, the input starves: more
+    /// tokens may have matched.
+    fn skip<F>(&mut self, cond: F) -> usize
+        where F: FnMut(&Self::Token) -> bool
+    {
+        let skipped = self.input.skip(cond);
+        if !self.input.has(1) {
+            self.starve(None);
+        }
+
+        skipped
+    }
+
+    /// Returns `true` if there are at least `n` tokens remaining.
+    fn has(&mut self, n: usize) -> bool {
+        let has = self.input.has(n);
+        if !has {
+            self.starve_for(n);
+        }
+
+        has
+    }
+
+    #[inline(always)]
+    fn mark(&mut self, info: &ParserInfo) -> Self::Marker {
+        self.input.mark(info)
+    }
+
+    #[inline(always)]
+    fn context(&mut self, mark: Self::Marker) -> Self::Context {
+        self.input.context(mark)
+    }
+
+    #[inline(always)]
+    fn is_partial(&mut self) -> bool {
+        !self.complete
+    }
+
+    #[inline(always)]
+    fn ignores_case(&mut self) -> bool {
+        self.input.ignores_case()
+    }
+
+    #[inline(always)]
+    fn malformed(&mut self) -> Option<(&'static str, usize)> {
+        self.input.malformed()
+    }
+}
+
+impl<I: Input + Rewind> Rewind for Partial<I> {
+    fn rewind_to(&mut self, marker: Self::Marker) {
+        self.input.rewind_to(marker)
+    }
+}
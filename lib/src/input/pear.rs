@@ -1,6 +1,11 @@
 use std::fmt;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
 
-use crate::input::{Input, Rewind, ParserInfo};
+use crate::input::{Input, Rewind, ParserInfo, Show, Length};
+use crate::input::suggest::Suggestions;
+use crate::error::{ParseError, Failure, Expected};
+use crate::catalog::{Localize, English};
 
 pub trait Debugger<I: Input> {
     fn on_entry(&mut self, info: &ParserInfo);
@@ -10,6 +15,83 @@ pub trait Debugger<I: Input> {
 pub struct Options<I> {
     pub stacked_context: bool,
     pub debugger: Option<Box<dyn Debugger<I>>>,
+    /// The maximum number of nested `#[parser]` calls. A parser entered at a
+    /// deeper level fails with `Expected::TooDeep` instead of running.
+    pub max_depth: Option<usize>,
+    /// The remaining fuel. One unit is spent on entry to every `#[parser]`,
+    /// one for every token consumed by `eat`, `take`, and `skip`, and one for
+    /// every unit of length of a slice consumed by `eat_slice`. Parsing fails
+    /// with [`Limit::Fuel`] once it runs out.
+    pub fuel: Option<u64>,
+    /// The maximum length of the input, in the units of [`Input::has()`]:
+    /// tokens for most inputs, but UTF-8 bytes for text inputs whose tokens
+    /// are `char`s, like `&str`, [`Text`](crate::input::Text), and
+    /// [`Utf8Bytes`](crate::input::Utf8Bytes), and bits for
+    /// [`Bits`](crate::input::Bits). Parsing fails with
+    /// [`Limit::InputLength`] if the input is longer. The length of a
+    /// partial input is only checked once it is complete, so that checking it
+    /// doesn't starve the parser.
+    pub max_input_length: Option<usize>,
+    /// A flag checked on entry to every `#[parser]`. Parsing fails with
+    /// [`Limit::Cancelled`] once it is set.
+    pub cancel: Option<Arc<AtomicBool>>,
+    /// Which contexts are pushed onto the stack of an error when
+    /// `stacked_context` is set.
+    pub stack_policy: StackPolicy,
+    /// Whether failures that won't be reported, such as those of `switch!`
+    /// arms that aren't taken, are a [`Failure::Elided`] instead of a built
+    /// `ParseError`. See [`Result`](crate::result::Result). On by default; turn it off to see
+    /// why an alternative failed.
+    pub elide_failures: bool,
+}
+
+/// A policy for pruning the stack of contexts of a [`ParseError`].
+///
+/// With `stacked_context` set in [`Options`], every `#[parser]` a failure
+/// propagates through pushes its context onto the error's stack. The policy
+/// decides which of those contexts are kept, as they're pushed, so pruned
+/// contexts are never computed. The default policy keeps every context.
+///
+/// Independently of the policy, a parser attributed with
+/// `#[parser(hidden)]` never pushes its context, and one attributed with
+/// `#[parser(label = "name")]` pushes it under `name`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct StackPolicy {
+    /// Omit the contexts of `raw` parsers, such as those in
+    /// [`parsers`](crate::parsers).
+    pub hide_raw: bool,
+    /// Collapse consecutive contexts of parsers with the same name into the
+    /// innermost one, counting them in its [`ErrorInfo::count`].
+    ///
+    /// [`ErrorInfo::count`]: crate::error::ErrorInfo::count
+    pub collapse: bool,
+    /// Keep at most this many of the innermost contexts.
+    pub max_frames: Option<usize>,
+}
+
+impl StackPolicy {
+    /// A policy that hides `raw` parsers and collapses repeated frames.
+    pub fn concise() -> Self {
+        StackPolicy { hide_raw: true, collapse: true, max_frames: None }
+    }
+
+    /// Returns `self` with [`StackPolicy::hide_raw`] set to `hide`.
+    pub fn hide_raw(mut self, hide: bool) -> Self {
+        self.hide_raw = hide;
+        self
+    }
+
+    /// Returns `self` with [`StackPolicy::collapse`] set to `collapse`.
+    pub fn collapse(mut self, collapse: bool) -> Self {
+        self.collapse = collapse;
+        self
+    }
+
+    /// Returns `self` keeping at most `max` frames.
+    pub fn max_frames(mut self, max: usize) -> Self {
+        self.max_frames = Some(max);
+        self
+    }
 }
 
 impl<I> fmt::Debug for Options<I> {
@@ -17,6 +99,12 @@ impl<I> fmt::Debug for Options<I> {
         f.debug_struct("Options")
             .field("stacked_context", &self.stacked_context)
             .field("debugger", &self.debugger.is_some())
+            .field("max_depth", &self.max_depth)
+            .field("fuel", &self.fuel)
+            .field("max_input_length", &self.max_input_length)
+            .field("cancel", &self.cancel)
+            .field("stack_policy", &self.stack_policy)
+            .field("elide_failures", &self.elide_failures)
             .finish()
     }
 }
@@ -29,6 +117,12 @@ impl<I: Input> Default for Options<I> {
         Options {
             stacked_context: true,
             debugger: Some(debugger),
+            max_depth: None,
+            fuel: None,
+            max_input_length: None,
+            cancel: None,
+            stack_policy: StackPolicy::default(),
+            elide_failures: true,
         }
     }
 
@@ -37,17 +131,180 @@ impl<I: Input> Default for Options<I> {
         Options {
             stacked_context: false,
             debugger: None,
+            max_depth: None,
+            fuel: None,
+            max_input_length: None,
+            cancel: None,
+            stack_policy: StackPolicy::default(),
+            elide_failures: true,
+        }
+    }
+}
+
+/// A parsing limit set in [`Options`] that was exceeded.
+///
+/// Once a limit is exceeded, every parser fails with
+/// `Expected::LimitExceeded`, and the failure is not backtracked: `switch!`
+/// and [`try_collect()`](crate::combinators::try_collect()) propagate it
+/// instead of trying alternatives.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Limit {
+    /// The fuel budget ran out.
+    Fuel,
+    /// The input is longer than the maximum length. See
+    /// [`Options::max_input_length`] for its units.
+    InputLength(usize),
+    /// The cancellation flag was set.
+    Cancelled,
+}
+
+impl fmt::Display for Limit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.localize(f, &English)
+    }
+}
+
+/// Runs `f`, first growing the stack onto the heap if little of it remains.
+/// Without the `stacker` feature, this simply runs `f`.
+#[doc(hidden)]
+#[inline(always)]
+pub fn grow_stack<R, F: FnOnce() -> R>(f: F) -> R {
+    #[cfg(feature = "stacker")] {
+        const RED_ZONE: usize = 64 * 1024;
+        const SEGMENT_SIZE: usize = 1024 * 1024;
+        stacker::maybe_grow(RED_ZONE, SEGMENT_SIZE, f)
+    }
+
+    #[cfg(not(feature = "stacker"))] {
+        f()
+    }
+}
+
+/// A version of the user state of a [`Pear`] that can be restored.
+///
+/// Returned by [`Pear::checkpoint_state()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StateCheckpoint(u64);
+
+/// User state and the saved versions of it that live checkpoints may restore.
+///
+/// A version is saved only when the state is about to be mutated while a
+/// checkpoint refers to it, so checkpoints that are released without the state
+/// changing cost nothing but a push and a pop.
+#[derive(Debug)]
+struct Journal<U> {
+    value: U,
+    version: u64,
+    next_version: u64,
+    pins: Vec<u64>,
+    saved: Vec<(u64, U)>,
+}
+
+impl<U> Journal<U> {
+    fn new(value: U) -> Self {
+        Journal { value, version: 0, next_version: 1, pins: vec![], saved: vec![] }
+    }
+
+    fn value_mut(&mut self) -> &mut U where U: Clone {
+        if self.pins.last() == Some(&self.version) {
+            self.saved.push((self.version, self.value.clone()));
+            self.version = self.next_version;
+            self.next_version += 1;
+        }
+
+        &mut self.value
+    }
+
+    fn checkpoint(&mut self) -> StateCheckpoint {
+        self.pins.push(self.version);
+        StateCheckpoint(self.version)
+    }
+
+    fn restore(&mut self, checkpoint: StateCheckpoint) {
+        if self.version == checkpoint.0 {
+            return;
+        }
+
+        let i = self.saved.partition_point(|(version, _)| *version <= checkpoint.0);
+        self.saved.truncate(i);
+        match self.saved.pop() {
+            Some((version, value)) if version == checkpoint.0 => {
+                self.value = value;
+                self.version = version;
+            }
+            Some(entry) => self.saved.push(entry),
+            None => {}
         }
     }
+
+    fn release(&mut self, checkpoint: StateCheckpoint) {
+        if let Some(i) = self.pins.iter().rposition(|&v| v == checkpoint.0) {
+            self.pins.truncate(i);
+        }
+
+        let live = self.pins.last().map_or(0, |&v| v + 1);
+        let i = self.saved.partition_point(|(version, _)| *version < live);
+        self.saved.truncate(i);
+    }
 }
 
+/// The input to a parser: an [`Input`] `I`, parsing options, and user state
+/// `U`.
+///
+/// The user state is a slot for the data context-sensitive grammars need, such
+/// as symbol tables or nesting counters. It is read with [`Pear::state()`] and
+/// modified with [`Pear::state_mut()`] from `#[parser]` bodies. Changes to the
+/// state are undone whenever a parser rewinds the input: the state is restored
+/// to its value when the marker that is rewound to was set by
+/// `#[parser(rewind)]` or `#[parser(peek)]`, and to its value before a failed
+/// `switch!` case was tried.
+///
+/// ```rust
+/// use pear::input::{Pear, Text};
+/// use pear::macros::{parser, switch, parse};
+/// use pear::parsers::*;
+///
+/// type Input<'a> = Pear<Text<'a>, Vec<&'a str>>;
+/// type Result<'a, T> = pear::input::Result<T, Text<'a>>;
+///
+/// #[parser(rewind)]
+/// fn declaration<'a>(input: &mut Input<'a>) -> Result<'a, ()> {
+///     let name = take_some_while(|c| c.is_alphabetic())?;
+///     input.state_mut().push(name);
+///     eat(';')?;
+/// }
+///
+/// #[parser]
+/// fn declarations<'a>(input: &mut Input<'a>) -> Result<'a, ()> {
+///     switch! {
+///         declaration() => declarations()?,
+///         _ => ()
+///     }
+/// }
+///
+/// let mut input = Pear::with_state(Text::from("a;b;c"), vec![]);
+/// assert!(parse!(declarations: &mut input).is_err());
+/// assert_eq!(input.state(), &["a", "b"]);
+/// ```
 #[derive(Debug)]
-pub struct Pear<I: Input> {
+pub struct Pear<I: Input, U = ()> {
     pub input: I,
     #[doc(hidden)]
     pub emit_error: bool,
     #[doc(hidden)]
-    pub options: Options<I>
+    pub options: Options<I>,
+    #[doc(hidden)]
+    pub depth: usize,
+    #[doc(hidden)]
+    pub committed: bool,
+    #[doc(hidden)]
+    pub fatal: bool,
+    exceeded: Option<Limit>,
+    length_checked: bool,
+    suggestions: Option<Box<Suggestions<I>>>,
+    state: Journal<U>,
 }
 
 impl<I: Input> Pear<I> {
@@ -58,24 +315,317 @@ impl<I: Input> Pear<I> {
 
 impl<I: Input> From<I> for Pear<I> {
     fn from(input: I) -> Pear<I> {
-        Pear { input, emit_error: true, options: Options::default() }
+        Pear::with_state(input, ())
     }
 }
 
-impl<I: Input> std::ops::Deref for Pear<I> {
+impl<I: Input, U> Pear<I, U> {
+    /// Creates a `Pear` over `input` with the user state `state`.
+    pub fn with_state<A>(input: A, state: U) -> Pear<I, U> where I: From<A> {
+        Pear {
+            input: I::from(input),
+            emit_error: true,
+            options: Options::default(),
+            depth: 0,
+            committed: false,
+            fatal: false,
+            exceeded: None,
+            length_checked: false,
+            suggestions: None,
+            state: Journal::new(state),
+        }
+    }
+
+    /// Replaces the options. A limit exceeded or a fatal failure under the
+    /// old options is forgotten, so parsing can resume under the new ones.
+    pub fn set_options(&mut self, options: Options<I>) {
+        self.options = options;
+        self.reset_limits();
+    }
+
+    /// Limits the nesting of `#[parser]` calls to `limit` levels. A parser
+    /// entered deeper than that fails with [`Expected::TooDeep`] instead of
+    /// running, so that deeply nested input can't overflow the stack.
+    ///
+    /// [`Expected::TooDeep`]: crate::error::Expected::TooDeep
+    pub fn with_max_depth(mut self, limit: usize) -> Self {
+        self.options.max_depth = Some(limit);
+        self.reset_limits();
+        self
+    }
+
+    /// Limits parsing to `fuel` units of work. See [`Options::fuel`].
+    pub fn with_fuel(mut self, fuel: u64) -> Self {
+        self.options.fuel = Some(fuel);
+        self.reset_limits();
+        self
+    }
+
+    /// Limits the length of the input to `max`. See
+    /// [`Options::max_input_length`] for its units.
+    pub fn with_max_input_length(mut self, max: usize) -> Self {
+        self.options.max_input_length = Some(max);
+        self.reset_limits();
+        self
+    }
+
+    /// Cancels parsing once `flag` is set. See [`Options::cancel`].
+    pub fn with_cancellation(mut self, flag: Arc<AtomicBool>) -> Self {
+        self.options.cancel = Some(flag);
+        self.reset_limits();
+        self
+    }
+
+    /// Prunes the stacks of errors with `policy`. See [`StackPolicy`].
+    pub fn with_stack_policy(mut self, policy: StackPolicy) -> Self {
+        self.options.stack_policy = policy;
+        self
+    }
+
+    /// Suggests fixes for errors: collects the literals that [`eat()`],
+    /// [`eat_slice()`], and [`eat_slice_ignore_case()`] expected, including in
+    /// `switch!` arms, since the input last advanced. When the outermost
+    /// `#[parser]` fails and its error is reported, the literal closest to
+    /// the input where they were expected, if any, is attached to the error
+    /// as a [`Suggestion`] to replace it, with a help message such as "did you
+    /// mean `false`?". Raw parsers, like those in [`parsers`], never suggest.
+    ///
+    /// Only text literals, those whose [`Show::literal()`] is `Some`, are
+    /// collected.
+    ///
+    /// ```rust
+    /// use pear::input::{Pear, Text, Result};
+    /// use pear::macros::{parser, switch, parse_error};
+    /// use pear::parsers::*;
+    ///
+    /// #[parser]
+    /// fn boolean<'a>(input: &mut Pear<Text<'a>>) -> Result<bool, Text<'a>> {
+    ///     switch! {
+    ///         eat_slice("true") => true,
+    ///         eat_slice("false") => false,
+    ///         _ => parse_error!("expected a boolean")?
+    ///     }
+    /// }
+    ///
+    /// let mut input = Pear::<Text<'_>>::new("fasle").with_suggestions();
+    /// let error = boolean(&mut input).unwrap_err().unwrap();
+    /// assert_eq!(error.help(), Some("did you mean `false`?"));
+    /// assert_eq!(error.suggestions()[0].replacement, "false");
+    /// assert_eq!(error.suggestions()[0].context.end, (1, 6, 5));
+    /// ```
+    ///
+    /// [`eat()`]: crate::parsers::eat()
+    /// [`eat_slice()`]: crate::parsers::eat_slice()
+    /// [`eat_slice_ignore_case()`]: crate::parsers::eat_slice_ignore_case()
+    /// [`Suggestion`]: crate::error::Suggestion
+    /// [`parsers`]: crate::parsers
+    pub fn with_suggestions(mut self) -> Self where I: Rewind {
+        self.suggestions = Some(Box::new(Suggestions::new()));
+        self
+    }
+
+    /// Records that `literal` was expected at the current position, for
+    /// suggestions.
+    #[doc(hidden)]
+    #[inline(always)]
+    pub fn expected_literal<L: Show + ?Sized>(&mut self, literal: &L) {
+        if let Some(ref mut suggestions) = self.suggestions {
+            suggestions.record(&mut self.input, literal);
+        }
+    }
+
+    /// Enters a speculative branch, such as a `switch!` arm, whose failure is
+    /// discarded unless it's fatal. Returns the previous `emit_error`, to be
+    /// restored when the branch is done.
+    #[doc(hidden)]
+    #[inline(always)]
+    pub fn speculate(&mut self) -> bool {
+        let emit_error = self.emit_error;
+        self.emit_error &= !self.options.elide_failures;
+        self.fatal = false;
+        emit_error
+    }
+
+    /// Returns the error of `failure`, the failure of the parser `info` run at
+    /// the top level, where failures are reported. A failure is only elided
+    /// at the top level if a parser returns one from a speculative branch it
+    /// ran; it's reported as [`Expected::Elided`] at the current position.
+    #[doc(hidden)]
+    #[cold]
+    pub fn report<E>(
+        &mut self,
+        info: ParserInfo,
+        failure: Failure<ParseError<I::Context, E>>
+    ) -> ParseError<I::Context, E>
+        where E: From<Expected<I::Token, I::Slice>>
+    {
+        match failure {
+            Failure::Error(error) => error,
+            Failure::Elided => {
+                let mark = self.input.mark(&info);
+                let context = self.input.context(mark);
+                ParseError::new(info, Expected::Elided.into(), context)
+            }
+        }
+    }
+
+    /// Attaches a suggestion to `error` from the literals expected at the
+    /// point of failure, if suggestions are enabled.
+    #[doc(hidden)]
+    #[inline]
+    pub fn suggest<E>(&mut self, error: &mut ParseError<I::Context, E>) {
+        if let Some(ref suggestions) = self.suggestions {
+            suggestions.suggest(&mut self.input, error);
+        }
+    }
+
+    #[inline(always)]
+    fn advanced(&mut self) {
+        if let Some(ref mut suggestions) = self.suggestions {
+            suggestions.clear();
+        }
+    }
+
+    /// Returns the limit that was exceeded, if any. Once a limit is exceeded,
+    /// every parser fails.
+    #[inline(always)]
+    pub fn exceeded(&self) -> Option<Limit> {
+        self.exceeded
+    }
+
+    /// Returns `true` if the last parser that failed did so fatally: because
+    /// it failed after committing with `#[parser(commit)]` or
+    /// [`parse_commit!()`], because a limit was exceeded, or because the
+    /// nesting depth exceeded `max_depth`. Fatal failures are not backtracked.
+    ///
+    /// [`parse_commit!()`]: crate::macros::parse_commit
+    #[inline(always)]
+    pub fn is_fatal(&self) -> bool {
+        self.fatal || self.exceeded.is_some()
+    }
+
+    /// Commits the current parser. See [`parse_commit!()`].
+    ///
+    /// [`parse_commit!()`]: crate::macros::parse_commit
+    #[doc(hidden)]
+    #[inline(always)]
+    pub fn commit(&mut self) {
+        self.committed = true;
+        self.emit_error = true;
+    }
+
+    /// Checks the limits in [`Options`] on entry to a parser, spending a unit
+    /// of fuel. Returns the limit that is exceeded, if any.
+    #[doc(hidden)]
+    #[inline]
+    pub fn check_limits(&mut self) -> Option<Limit> {
+        if self.exceeded.is_none() {
+            self.exceeded = self.exceeded_limit();
+        }
+
+        self.exceeded
+    }
+
+    fn exceeded_limit(&mut self) -> Option<Limit> {
+        if let Some(ref cancel) = self.options.cancel {
+            if cancel.load(Ordering::Relaxed) {
+                return Some(Limit::Cancelled);
+            }
+        }
+
+        let checked = self.length_checked || self.input.is_partial();
+        if let (Some(max), false) = (self.options.max_input_length, checked) {
+            self.length_checked = true;
+            if self.input.has(max.saturating_add(1)) {
+                return Some(Limit::InputLength(max));
+            }
+        }
+
+        match self.options.fuel {
+            Some(0) => Some(Limit::Fuel),
+            Some(ref mut fuel) => { *fuel -= 1; None }
+            None => None,
+        }
+    }
+
+    fn reset_limits(&mut self) {
+        self.exceeded = None;
+        self.fatal = false;
+        self.length_checked = false;
+    }
+
+    #[inline(always)]
+    fn spend(&mut self, tokens: usize) {
+        if let Some(ref mut fuel) = self.options.fuel {
+            *fuel = fuel.saturating_sub(tokens as u64);
+        }
+    }
+
+    /// Returns a reference to the user state.
+    #[inline(always)]
+    pub fn state(&self) -> &U {
+        &self.state.value
+    }
+
+    /// Returns a mutable reference to the user state. If a checkpoint refers
+    /// to the current state, a copy of it is saved first so that it can be
+    /// restored.
+    #[inline]
+    pub fn state_mut(&mut self) -> &mut U where U: Clone {
+        match std::mem::size_of::<U>() {
+            0 => &mut self.state.value,
+            _ => self.state.value_mut(),
+        }
+    }
+
+    /// Consumes `self` and returns the user state.
+    pub fn into_state(self) -> U {
+        self.state.value
+    }
+
+    /// Returns a checkpoint for the current user state. Every checkpoint must
+    /// eventually be released with [`Pear::release_state()`]; checkpoints must
+    /// be released in the reverse order in which they were taken.
+    #[inline]
+    pub fn checkpoint_state(&mut self) -> StateCheckpoint {
+        match std::mem::size_of::<U>() {
+            0 => StateCheckpoint(0),
+            _ => self.state.checkpoint(),
+        }
+    }
+
+    /// Restores the user state to its value when `checkpoint` was taken.
+    /// `checkpoint` remains live and may be restored again.
+    #[inline]
+    pub fn restore_state(&mut self, checkpoint: StateCheckpoint) {
+        if std::mem::size_of::<U>() != 0 {
+            self.state.restore(checkpoint);
+        }
+    }
+
+    /// Releases `checkpoint` and any checkpoints taken after it.
+    #[inline]
+    pub fn release_state(&mut self, checkpoint: StateCheckpoint) {
+        if std::mem::size_of::<U>() != 0 {
+            self.state.release(checkpoint);
+        }
+    }
+}
+
+impl<I: Input, U> std::ops::Deref for Pear<I, U> {
     type Target = I;
     fn deref(&self) -> &Self::Target {
         &self.input
     }
 }
 
-impl<I: Input> std::ops::DerefMut for Pear<I> {
+impl<I: Input, U> std::ops::DerefMut for Pear<I, U> {
     fn deref_mut(&mut self) -> &mut Self::Target {
         &mut self.input
     }
 }
 
-impl<I: Input> Input for Pear<I> {
+impl<I: Input, U> Input for Pear<I, U> {
     type Token = I::Token;
     type Slice = I::Slice;
     type Many = I::Many;
@@ -116,28 +666,67 @@ impl<I: Input> Input for Pear<I> {
     fn eat<F>(&mut self, cond: F) -> Option<Self::Token>
         where F: FnMut(&Self::Token) -> bool
     {
-        self.input.eat(cond)
+        let token = self.input.eat(cond);
+        if token.is_some() {
+            self.spend(1);
+            self.advanced();
+        }
+
+        token
     }
 
     #[inline(always)]
     fn eat_slice<F>(&mut self, n: usize, cond: F) -> Option<Self::Slice>
         where F: FnMut(&Self::Slice) -> bool
     {
-        self.input.eat_slice(n, cond)
+        let slice = self.input.eat_slice(n, cond);
+        if slice.is_some() {
+            self.spend(n);
+            self.advanced();
+        }
+
+        slice
     }
 
     #[inline(always)]
-    fn take<F>(&mut self, cond: F) -> Self::Many
+    fn take<F>(&mut self, mut cond: F) -> Self::Many
         where F: FnMut(&Self::Token) -> bool
     {
-        self.input.take(cond)
+        if self.options.fuel.is_none() {
+            let many = self.input.take(cond);
+            if !many.is_empty() {
+                self.advanced();
+            }
+
+            return many;
+        }
+
+        let mut taken = 0;
+        let many = self.input.take(|t| {
+            let take = cond(t);
+            taken += take as usize;
+            take
+        });
+
+        self.spend(taken);
+        if taken > 0 {
+            self.advanced();
+        }
+
+        many
     }
 
     #[inline(always)]
     fn skip<F>(&mut self, cond: F) -> usize
         where F: FnMut(&Self::Token) -> bool
     {
-        self.input.skip(cond)
+        let skipped = self.input.skip(cond);
+        self.spend(skipped);
+        if skipped > 0 {
+            self.advanced();
+        }
+
+        skipped
     }
 
     #[inline(always)]
@@ -149,10 +738,29 @@ impl<I: Input> Input for Pear<I> {
     fn context(&mut self, mark: Self::Marker) -> Self::Context {
         self.input.context(mark)
     }
+
+    #[inline(always)]
+    fn is_partial(&mut self) -> bool {
+        self.input.is_partial()
+    }
+
+    #[inline(always)]
+    fn ignores_case(&mut self) -> bool {
+        self.input.ignores_case()
+    }
+
+    #[inline(always)]
+    fn malformed(&mut self) -> Option<(&'static str, usize)> {
+        self.input.malformed()
+    }
 }
 
-impl<I: Input + Rewind> Rewind for Pear<I> {
+impl<I: Input + Rewind, U> Rewind for Pear<I, U> {
     fn rewind_to(&mut self, marker: Self::Marker) {
+        if let Some(ref mut suggestions) = self.suggestions {
+            suggestions.rewound();
+        }
+
         self.input.rewind_to(marker)
     }
 }
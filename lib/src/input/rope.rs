@@ -0,0 +1,153 @@
+use std::borrow::Cow;
+
+use ropey::{Rope, RopeSlice};
+
+use crate::input::{Input, Rewind, ParserInfo, Span};
+
+/// An input over a [`ropey`] rope with `char` tokens.
+///
+/// Slices borrow from the rope when they lie within a single chunk and are
+/// copied otherwise. Like [`Text`](crate::input::Text), lengths and markers
+/// are in bytes. Contexts are [`Span`]s whose lines and columns are computed
+/// from the rope's line index. A context has a snippet only if it lies within
+/// a single rope chunk.
+#[derive(Debug, Clone)]
+pub struct RopeInput<'a> {
+    rope: RopeSlice<'a>,
+    offset: usize,
+}
+
+impl<'a> From<RopeSlice<'a>> for RopeInput<'a> {
+    fn from(rope: RopeSlice<'a>) -> RopeInput<'a> {
+        RopeInput { rope, offset: 0 }
+    }
+}
+
+impl<'a> From<&'a Rope> for RopeInput<'a> {
+    fn from(rope: &'a Rope) -> RopeInput<'a> {
+        RopeInput::from(rope.slice(..))
+    }
+}
+
+impl<'a> RopeInput<'a> {
+    fn remaining(&self) -> RopeSlice<'a> {
+        self.rope.byte_slice(self.offset..)
+    }
+
+    fn line_col(&self, offset: usize) -> (usize, usize, usize) {
+        let line = self.rope.byte_to_line(offset);
+        (line + 1, offset - self.rope.line_to_byte(line) + 1, offset)
+    }
+}
+
+impl Rewind for RopeInput<'_> {
+    fn rewind_to(&mut self, marker: Self::Marker) {
+        self.offset = marker;
+    }
+}
+
+impl<'a> Input for RopeInput<'a> {
+    type Token = char;
+    type Slice = Cow<'a, str>;
+    type Many = Self::Slice;
+
+    type Marker = usize;
+    type Context = Span<'a>;
+
+    /// Returns a copy of the current token, if there is one.
+    fn token(&mut self) -> Option<Self::Token> {
+        self.remaining().chars().next()
+    }
+
+    /// Returns a copy of the current slice of size `n`, if there is one.
+    fn slice(&mut self, n: usize) -> Option<Self::Slice> {
+        let end = self.offset.checked_add(n)?;
+        self.rope.get_byte_slice(self.offset..end).map(Cow::from)
+    }
+
+    /// Checks if the current token fulfills `cond`.
+    fn peek<F>(&mut self, mut cond: F) -> bool
+        where F: FnMut(&Self::Token) -> bool
+    {
+        self.token().map(|t| cond(&t)).unwrap_or(false)
+    }
+
+    /// Checks if the current slice of size `n` (if any) fulfills `cond`.
+    fn peek_slice<F>(&mut self, n: usize, mut cond: F) -> bool
+        where F: FnMut(&Self::Slice) -> bool
+    {
+        self.slice(n).map(|s| cond(&s)).unwrap_or(false)
+    }
+
+    /// Checks if the current token fulfills `cond`. If so, the token is
+    /// consumed and returned. Otherwise, returns `None`.
+    fn eat<F>(&mut self, mut cond: F) -> Option<Self::Token>
+        where F: FnMut(&Self::Token) -> bool
+    {
+        let token = self.token()?;
+        if cond(&token) {
+            self.offset += token.len_utf8();
+            Some(token)
+        } else {
+            None
+        }
+    }
+
+    /// Checks if the current slice of size `n` (if any) fulfills `cond`. If so,
+    /// the slice is consumed and returned. Otherwise, returns `None`.
+    fn eat_slice<F>(&mut self, n: usize, mut cond: F) -> Option<Self::Slice>
+        where F: FnMut(&Self::Slice) -> bool
+    {
+        let slice = self.slice(n)?;
+        if cond(&slice) {
+            self.offset += n;
+            Some(slice)
+        } else {
+            None
+        }
+    }
+
+    /// Takes tokens while `cond` returns true, collecting them into a
+    /// `Self::Many` and returning it.
+    fn take<F>(&mut self, cond: F) -> Self::Many
+        where F: FnMut(&Self::Token) -> bool
+    {
+        let start = self.offset;
+        self.skip(cond);
+        self.rope.byte_slice(start..self.offset).into()
+    }
+
+    /// Skips tokens while `cond` returns true. Returns the number of skipped
+    /// bytes, as for `&str`.
+    fn skip<F>(&mut self, mut cond: F) -> usize
+        where F: FnMut(&Self::Token) -> bool
+    {
+        let skipped: usize = self.remaining().chars()
+            .take_while(|c| cond(c))
+            .map(char::len_utf8)
+            .sum();
+
+        self.offset += skipped;
+        skipped
+    }
+
+    /// Returns `true` if there are at least `n` tokens remaining.
+    fn has(&mut self, n: usize) -> bool {
+        self.rope.len_bytes() - self.offset >= n
+    }
+
+    #[inline(always)]
+    fn mark(&mut self, _: &ParserInfo) -> Self::Marker {
+        self.offset
+    }
+
+    fn context(&mut self, mark: Self::Marker) -> Self::Context {
+        let snippet = self.rope.byte_slice(mark..self.offset);
+        Span {
+            start: self.line_col(mark),
+            end: self.line_col(self.offset),
+            cursor: self.token(),
+            snippet: snippet.as_str(),
+        }
+    }
+}
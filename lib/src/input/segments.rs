@@ -0,0 +1,233 @@
+use std::borrow::Cow;
+use std::collections::VecDeque;
+
+use crate::input::{Input, Rewind, ParserInfo, Extent};
+
+/// An input over a sequence of non-contiguous byte segments.
+///
+/// `Segments` parses data that arrives as a list of chunks, such as a
+/// `VecDeque<Bytes>` or a list of iovecs, without first coalescing it into a
+/// single buffer. Slices are [`Extent`]s whose values borrow from a segment
+/// when they lie within one and are copied only when they span several.
+/// Offsets, markers, and lengths are in bytes from the start of the first
+/// segment.
+///
+/// ```rust
+/// use pear::input::{Pear, Segments};
+/// use pear::parsers::*;
+///
+/// let chunks: &[&[u8]] = &[b"GE", b"T /", b"index"];
+/// let mut input: Pear<Segments<'_>> = Pear::new(Segments::new(chunks));
+///
+/// let method = eat_slice(&mut input, &b"GET"[..]).unwrap();
+/// assert_eq!(method, &b"GET"[..]);
+///
+/// eat(&mut input, b' ').unwrap();
+/// let path = take_while(&mut input, |_| true).unwrap();
+/// assert_eq!((path.start, path.end), (4, 10));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Segments<'a> {
+    segments: Vec<&'a [u8]>,
+    starts: Vec<usize>,
+    len: usize,
+    offset: usize,
+    index: usize,
+}
+
+impl<'a> Segments<'a> {
+    /// Creates an input over `segments`, in order. Empty segments are ignored.
+    pub fn new<I, S>(segments: I) -> Segments<'a>
+        where I: IntoIterator<Item = &'a S>, S: AsRef<[u8]> + ?Sized + 'a
+    {
+        let mut len = 0;
+        let (mut starts, mut slices) = (vec![], vec![]);
+        for segment in segments.into_iter().map(|s| s.as_ref()) {
+            if !segment.is_empty() {
+                starts.push(len);
+                slices.push(segment);
+                len += segment.len();
+            }
+        }
+
+        Segments { segments: slices, starts, len, offset: 0, index: 0 }
+    }
+
+    /// Returns the index of the segment containing `offset`, or the number of
+    /// segments if `offset` is at or past the end of the input.
+    fn segment_at(&self, offset: usize) -> usize {
+        match offset >= self.len {
+            true => self.segments.len(),
+            false => self.starts.partition_point(|&start| start <= offset) - 1,
+        }
+    }
+
+    /// Returns the `n` bytes starting at `offset`, borrowing them if they lie
+    /// within a single segment.
+    fn bytes(&self, offset: usize, n: usize) -> Option<Cow<'a, [u8]>> {
+        if offset.checked_add(n)? > self.len {
+            return None;
+        }
+
+        let index = self.segment_at(offset);
+        let segment = match self.segments.get(index) {
+            Some(segment) => segment,
+            None => return Some(Cow::Borrowed(&[])),
+        };
+
+        let inner = offset - self.starts[index];
+        if inner + n <= segment.len() {
+            return Some(Cow::Borrowed(&segment[inner..(inner + n)]));
+        }
+
+        let mut bytes = Vec::with_capacity(n);
+        bytes.extend_from_slice(&segment[inner..]);
+        for segment in &self.segments[(index + 1)..] {
+            let remaining = n - bytes.len();
+            if remaining == 0 { break; }
+            bytes.extend_from_slice(&segment[..remaining.min(segment.len())]);
+        }
+
+        Some(Cow::Owned(bytes))
+    }
+
+    fn extent(&self, start: usize, n: usize) -> Option<Extent<Cow<'a, [u8]>>> {
+        let values = self.bytes(start, n)?;
+        Some(Extent { start, end: start + n, values })
+    }
+
+    fn advance(&mut self, n: usize) {
+        self.offset += n;
+        while let Some(segment) = self.segments.get(self.index) {
+            if self.offset < self.starts[self.index] + segment.len() {
+                break;
+            }
+
+            self.index += 1;
+        }
+    }
+
+    fn remaining(&self) -> impl Iterator<Item = u8> + '_ {
+        let inner = self.starts.get(self.index).map_or(0, |start| self.offset - start);
+        self.segments[self.index..].iter()
+            .enumerate()
+            .flat_map(move |(i, s)| if i == 0 { &s[inner..] } else { &s[..] })
+            .copied()
+    }
+}
+
+impl<'a, S: AsRef<[u8]>> From<&'a [S]> for Segments<'a> {
+    fn from(segments: &'a [S]) -> Segments<'a> {
+        Segments::new(segments)
+    }
+}
+
+impl<'a, S: AsRef<[u8]>> From<&'a VecDeque<S>> for Segments<'a> {
+    fn from(segments: &'a VecDeque<S>) -> Segments<'a> {
+        Segments::new(segments)
+    }
+}
+
+impl<'a> Input for Segments<'a> {
+    type Token = u8;
+    type Slice = Extent<Cow<'a, [u8]>>;
+    type Many = Self::Slice;
+
+    type Marker = usize;
+    type Context = Self::Slice;
+
+    /// Returns a copy of the current token, if there is one.
+    fn token(&mut self) -> Option<Self::Token> {
+        let segment = self.segments.get(self.index)?;
+        Some(segment[self.offset - self.starts[self.index]])
+    }
+
+    /// Returns a copy of the current slice of size `n`, if there is one.
+    fn slice(&mut self, n: usize) -> Option<Self::Slice> {
+        self.extent(self.offset, n)
+    }
+
+    /// Checks if the current token fulfills `cond`.
+    fn peek<F>(&mut self, mut cond: F) -> bool
+        where F: FnMut(&Self::Token) -> bool
+    {
+        self.token().map(|t| cond(&t)).unwrap_or(false)
+    }
+
+    /// Checks if the current slice of size `n` (if any) fulfills `cond`.
+    fn peek_slice<F>(&mut self, n: usize, mut cond: F) -> bool
+        where F: FnMut(&Self::Slice) -> bool
+    {
+        self.slice(n).map(|s| cond(&s)).unwrap_or(false)
+    }
+
+    /// Checks if the current token fulfills `cond`. If so, the token is
+    /// consumed and returned. Otherwise, returns `None`.
+    fn eat<F>(&mut self, mut cond: F) -> Option<Self::Token>
+        where F: FnMut(&Self::Token) -> bool
+    {
+        let token = self.token()?;
+        if cond(&token) {
+            self.advance(1);
+            Some(token)
+        } else {
+            None
+        }
+    }
+
+    /// Checks if the current slice of size `n` (if any) fulfills `cond`. If so,
+    /// the slice is consumed and returned. Otherwise, returns `None`.
+    fn eat_slice<F>(&mut self, n: usize, mut cond: F) -> Option<Self::Slice>
+        where F: FnMut(&Self::Slice) -> bool
+    {
+        let slice = self.slice(n)?;
+        if cond(&slice) {
+            self.advance(n);
+            Some(slice)
+        } else {
+            None
+        }
+    }
+
+    /// Takes tokens while `cond` returns true, collecting them into a
+    /// `Self::Many` and returning it.
+    fn take<F>(&mut self, mut cond: F) -> Self::Many
+        where F: FnMut(&Self::Token) -> bool
+    {
+        let n = self.remaining().take_while(|b| cond(b)).count();
+        let extent = self.extent(self.offset, n).expect("taken bytes exist");
+        self.advance(n);
+        extent
+    }
+
+    /// Skips tokens while `cond` returns true. Returns the number of skipped
+    /// tokens.
+    fn skip<F>(&mut self, mut cond: F) -> usize
+        where F: FnMut(&Self::Token) -> bool
+    {
+        let n = self.remaining().take_while(|b| cond(b)).count();
+        self.advance(n);
+        n
+    }
+
+    /// Returns `true` if there are at least `n` tokens remaining.
+    fn has(&mut self, n: usize) -> bool {
+        self.len - self.offset >= n
+    }
+
+    #[inline(always)]
+    fn mark(&mut self, _: &ParserInfo) -> Self::Marker {
+        self.offset
+    }
+
+    fn context(&mut self, mark: Self::Marker) -> Self::Context {
+        self.extent(mark, self.offset - mark).expect("marked bytes exist")
+    }
+}
+
+impl Rewind for Segments<'_> {
+    fn rewind_to(&mut self, marker: Self::Marker) {
+        self.offset = marker;
+        self.index = self.segment_at(marker);
+    }
+}
@@ -27,11 +27,22 @@
 //     itermediary parser will suggest emitting an error where a preceding
 //     parser known it doesn't need it?
 //
+//     Done: `pear::Result` stays a `Result`, but its error is a
+//     `Failure::{Elided, Error(E)}`, and parsers return `Failure::Elided` when
+//     `emit_error` is false. The `?` rewrite lifts other errors into it.
+//
 // TODO
 // * Print parser arguments in debug/error output.
 
 pub trait Show {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result;
+
+    /// Returns the value as plain text, without quotes or escapes, if it is
+    /// text. Used to compare expected and found literals for suggestions.
+    #[inline(always)]
+    fn literal(&self) -> Option<String> {
+        None
+    }
 }
 
 impl std::fmt::Display for &dyn Show {
@@ -46,6 +57,11 @@ impl<T: Show + ?Sized> Show for &T {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         <T as Show>::fmt(self, f)
     }
+
+    #[inline(always)]
+    fn literal(&self) -> Option<String> {
+        <T as Show>::literal(self)
+    }
 }
 
 impl<T: Show> Show for Option<T> {
@@ -76,6 +92,11 @@ impl<T: Show + ?Sized + ToOwned> Show for std::borrow::Cow<'_, T> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         Show::fmt(self.as_ref(), f)
     }
+
+    #[inline(always)]
+    fn literal(&self) -> Option<String> {
+        Show::literal(self.as_ref())
+    }
 }
 
 macro_rules! impl_for_slice_len {
@@ -112,6 +133,8 @@ impl Show for u8 {
     }
 }
 
+impl_show_with! { Display, bool }
+
 impl_show_with! { Debug,
         u16, u32, u64, u128, usize,
     i8, i16, i32, i64, i128, isize
@@ -124,10 +147,15 @@ macro_rules! impl_with_tick_display {
             fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
                 write!(f, "{:?}", self)
             }
+
+            #[inline(always)]
+            fn literal(&self) -> Option<String> {
+                Some(self.to_string())
+            }
         }
     )*)
 }
 
 impl_with_tick_display! {
-    &str, String, char, std::borrow::Cow<'static, str>,
+    &str, String, char, std::borrow::Cow<'_, str>,
 }
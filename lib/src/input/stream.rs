@@ -0,0 +1,122 @@
+use crate::input::{Input, Pear, Partial, ParserInfo};
+use crate::error::{ParseError, Expected};
+use crate::result::Result;
+
+/// The outcome of [`Stream::parse()`].
+#[derive(Debug)]
+pub enum Status<T, E> {
+    /// The parser succeeded and the input it consumed was committed.
+    Complete(T),
+    /// The parser ran out of buffered input. The number of additional tokens
+    /// required, if known, is a lower bound: more may be needed after they
+    /// arrive.
+    Incomplete(Option<usize>),
+    /// The parser failed on the buffered input.
+    Error(E),
+}
+
+/// A buffer for parsing input that arrives in pieces, such as from a socket.
+///
+/// Bytes are appended with [`Stream::feed()`]. Each call to [`Stream::parse()`]
+/// runs a parser from the last committed position over a [`Partial`] view of
+/// the buffered bytes. If the parser starves, [`Status::Incomplete`] is
+/// returned and nothing is committed, so the same parser can be retried once
+/// more bytes have been fed. When it succeeds, the position it reached becomes
+/// the new committed position.
+///
+/// ```rust
+/// use pear::input::{Cursor, Partial, Pear, Stream, Status};
+/// use pear::macros::parser;
+/// use pear::parsers::*;
+///
+/// type Input<'a> = Partial<Cursor<&'a [u8]>>;
+/// type Result<'a, T> = pear::input::Result<T, Input<'a>>;
+///
+/// #[parser]
+/// fn line<'a>(input: &mut Pear<Input<'a>>) -> Result<'a, &'a [u8]> {
+///     let line = take_while(|&b| b != b'\r')?;
+///     eat_slice(&b"\r\n"[..])?;
+///     line.values
+/// }
+///
+/// let mut stream = Stream::new();
+/// stream.feed(b"HTTP/1.1\r");
+/// assert!(matches!(stream.parse(line), Status::Incomplete(Some(1))));
+///
+/// stream.feed(b"\nHost");
+/// match stream.parse(line) {
+///     Status::Complete(line) => assert_eq!(line, b"HTTP/1.1"),
+///     _ => unreachable!("the line is complete"),
+/// }
+///
+/// assert_eq!(stream.buffered(), b"Host");
+/// ```
+#[derive(Debug, Default)]
+pub struct Stream {
+    buffer: Vec<u8>,
+    committed: usize,
+    complete: bool,
+}
+
+impl Stream {
+    /// Creates an empty stream.
+    pub fn new() -> Self {
+        Stream::default()
+    }
+
+    /// Appends `bytes` to the buffer, discarding any committed bytes.
+    pub fn feed(&mut self, bytes: &[u8]) {
+        self.buffer.drain(..self.committed);
+        self.committed = 0;
+        self.buffer.extend_from_slice(bytes);
+    }
+
+    /// Marks the stream as complete: no more bytes will be fed. Subsequent
+    /// parses treat the end of the buffer as the end of the input.
+    pub fn complete(&mut self) {
+        self.complete = true;
+    }
+
+    /// Returns `true` if [`Stream::complete()`] has been called.
+    pub fn is_complete(&self) -> bool {
+        self.complete
+    }
+
+    /// Returns the buffered bytes that have not been committed.
+    pub fn buffered(&self) -> &[u8] {
+        &self.buffer[self.committed..]
+    }
+
+    /// Runs `parser` on the uncommitted bytes.
+    ///
+    /// A parse that starved is reported as [`Status::Incomplete`], even if
+    /// the parser succeeded: its result may differ once more bytes arrive.
+    pub fn parse<'a, I, O, E, F>(&'a mut self, parser: F) -> Status<O, ParseError<I::Context, E>>
+        where I: Input<Marker = usize> + From<&'a [u8]>,
+              F: FnOnce(&mut Pear<Partial<I>>) -> Result<O, I::Context, E>,
+              E: From<Expected<I::Token, I::Slice>>
+    {
+        let Stream { buffer, committed, complete } = self;
+        let bytes: &'a [u8] = &buffer[*committed..];
+
+        let mut partial = Partial::new(I::from(bytes));
+        if *complete {
+            partial.complete();
+        }
+
+        let mut input = Pear::from(partial);
+        let result = parser(&mut input);
+        if input.is_starved() {
+            return Status::Incomplete(input.needed());
+        }
+
+        let info = ParserInfo { name: "stream", raw: true };
+        match result {
+            Ok(value) => {
+                *committed += input.mark(&info);
+                Status::Complete(value)
+            }
+            Err(e) => Status::Error(input.report(info, e)),
+        }
+    }
+}
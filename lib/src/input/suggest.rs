@@ -0,0 +1,173 @@
+use std::fmt;
+
+use crate::input::{Input, Rewind, ParserInfo, Show};
+use crate::error::ParseError;
+
+const INFO: ParserInfo = ParserInfo { name: "suggest", raw: true };
+
+/// Returns the text of the `n` tokens at `at` and its context, if the input
+/// there is text, leaving the input where it was.
+type SpanOf<I> = fn(&mut I, <I as Input>::Marker, usize) -> Option<(String, <I as Input>::Context)>;
+
+/// The literals that parsers expected since the input last advanced, and the
+/// position at which they were expected.
+pub(crate) struct Suggestions<I: Input> {
+    span_of: SpanOf<I>,
+    literals: Vec<String>,
+    at: Option<I::Marker>,
+    stale: bool,
+}
+
+impl<I: Input> fmt::Debug for Suggestions<I> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Suggestions")
+            .field("literals", &self.literals)
+            .field("stale", &self.stale)
+            .finish()
+    }
+}
+
+fn span_of<I: Rewind>(input: &mut I, at: I::Marker, n: usize) -> Option<(String, I::Context)> {
+    let here = input.mark(&INFO);
+    input.rewind_to(at);
+    let found = (0..n).try_fold(String::new(), |mut found, _| {
+        let text = input.token()?.literal()?;
+        input.eat(|_| true)?;
+        found.push_str(&text);
+        Some(found)
+    });
+
+    let found = found.map(|found| (found, input.context(at)));
+    input.rewind_to(here);
+    found
+}
+
+impl<I: Input> Suggestions<I> {
+    pub(crate) fn new() -> Self where I: Rewind {
+        Suggestions { span_of: span_of::<I>, literals: vec![], at: None, stale: false }
+    }
+
+    /// Records that `literal` was expected at the current position of `input`.
+    pub(crate) fn record<L: Show + ?Sized>(&mut self, input: &mut I, literal: &L) {
+        let literal = match literal.literal() {
+            Some(literal) => literal,
+            None => return,
+        };
+
+        if self.stale || self.at.is_none() {
+            self.literals.clear();
+            self.at = Some(input.mark(&INFO));
+            self.stale = false;
+        }
+
+        if !self.literals.contains(&literal) {
+            self.literals.push(literal);
+        }
+    }
+
+    /// Forgets the recorded literals: the input advanced past them.
+    #[inline(always)]
+    pub(crate) fn clear(&mut self) {
+        if self.at.is_some() {
+            self.literals.clear();
+            self.at = None;
+        }
+    }
+
+    /// Marks the recorded literals as stale: the input was rewound. They're
+    /// kept until another literal is recorded, so that a rewound failure can
+    /// still be suggested for.
+    #[inline(always)]
+    pub(crate) fn rewound(&mut self) {
+        self.stale = true;
+    }
+
+    /// Attaches a suggestion for the recorded literal closest to the input at
+    /// the position it was expected, if there is one that's close enough.
+    pub(crate) fn suggest<E>(&self, input: &mut I, error: &mut ParseError<I::Context, E>) {
+        let at = match self.at {
+            Some(at) if error.suggestions().is_empty() => at,
+            _ => return,
+        };
+
+        let mut best: Option<(usize, &str, I::Context)> = None;
+        for literal in &self.literals {
+            let n = literal.chars().count();
+            if n < 2 {
+                continue;
+            }
+
+            let max = std::cmp::max(1, n / 3);
+            for m in (n - 1)..=(n + 1) {
+                let (found, context) = match (self.span_of)(input, at, m) {
+                    Some(found) => found,
+                    None => continue,
+                };
+
+                let d = distance(&found, literal);
+                if d > 0 && d <= max && !matches!(best, Some((b, ..)) if b <= d) {
+                    best = Some((d, literal, context));
+                }
+            }
+        }
+
+        if let Some((_, literal, context)) = best {
+            if error.help().is_none() {
+                error.diagnostic_mut().help = Some(format!("did you mean `{}`?", literal).into());
+            }
+
+            error.diagnostic_mut().suggestions.push(crate::error::Suggestion {
+                context,
+                replacement: literal.to_string().into(),
+            });
+        }
+    }
+}
+
+/// The optimal string alignment distance between `a` and `b`: the number of
+/// character insertions, deletions, substitutions, and transpositions of
+/// adjacent characters needed to turn one into the other.
+fn distance(a: &str, b: &str) -> usize {
+    let (a, b): (Vec<char>, Vec<char>) = (a.chars().collect(), b.chars().collect());
+    let mut rows = vec![vec![0; b.len() + 1]; a.len() + 1];
+    for (i, row) in rows.iter_mut().enumerate() {
+        row[0] = i;
+    }
+
+    for (j, cell) in rows[0].iter_mut().enumerate() {
+        *cell = j;
+    }
+
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = (a[i - 1] != b[j - 1]) as usize;
+            let mut d = (rows[i - 1][j] + 1)
+                .min(rows[i][j - 1] + 1)
+                .min(rows[i - 1][j - 1] + cost);
+
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                d = d.min(rows[i - 2][j - 2] + 1);
+            }
+
+            rows[i][j] = d;
+        }
+    }
+
+    rows[a.len()][b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::distance;
+
+    #[test]
+    fn test_distance() {
+        assert_eq!(distance("false", "false"), 0);
+        assert_eq!(distance("fasle", "false"), 1);
+        assert_eq!(distance("fals", "false"), 1);
+        assert_eq!(distance("ture", "true"), 1);
+        assert_eq!(distance("nul", "null"), 1);
+        assert_eq!(distance("xyz", "null"), 4);
+        assert_eq!(distance("", "ab"), 2);
+    }
+}
@@ -1,6 +1,7 @@
-pub use crate::input::{Input, Rewind, Token, Slice, Show, ParserInfo};
+pub use crate::input::{Input, Rewind, Token, Slice, Show, ParserInfo, IntoOwned};
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Span<'a> {
     /// Start line/column/offset.
     pub start: (usize, usize, usize),
@@ -12,6 +13,13 @@ pub struct Span<'a> {
     pub snippet: Option<&'a str>,
 }
 
+impl Span<'_> {
+    /// The byte offsets of the start and end of the span.
+    pub fn range(&self) -> std::ops::Range<usize> {
+        self.start.2..self.end.2
+    }
+}
+
 const SNIPPET_LEN: usize = 30;
 
 impl<'a> Show for Span<'a> {
@@ -67,15 +75,85 @@ impl<'a> Show for Span<'a> {
     }
 }
 
+/// A [`Span`] that owns its snippet.
+///
+/// Used as the context of inputs whose text is decoded on the fly and so
+/// cannot be borrowed from the source.
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OwnedSpan {
+    /// Start line/column/offset.
+    pub start: (usize, usize, usize),
+    /// End line/column/offset.
+    pub end: (usize, usize, usize),
+    /// Where the parser was pointing.
+    pub cursor: Option<char>,
+    /// Snippet between start and end.
+    pub snippet: Option<String>,
+}
+
+impl OwnedSpan {
+    /// The byte offsets of the start and end of the span.
+    pub fn range(&self) -> std::ops::Range<usize> {
+        self.start.2..self.end.2
+    }
+
+    /// Returns a [`Span`] borrowing this span's snippet.
+    pub fn as_span(&self) -> Span<'_> {
+        Span {
+            start: self.start,
+            end: self.end,
+            cursor: self.cursor,
+            snippet: self.snippet.as_deref(),
+        }
+    }
+}
+
+impl From<Span<'_>> for OwnedSpan {
+    fn from(span: Span<'_>) -> OwnedSpan {
+        OwnedSpan {
+            start: span.start,
+            end: span.end,
+            cursor: span.cursor,
+            snippet: span.snippet.map(String::from),
+        }
+    }
+}
+
+impl IntoOwned for Span<'_> {
+    type Owned = OwnedSpan;
+
+    #[inline(always)]
+    fn into_owned(self) -> OwnedSpan {
+        OwnedSpan::from(self)
+    }
+}
+
+impl IntoOwned for OwnedSpan {
+    type Owned = OwnedSpan;
+
+    #[inline(always)]
+    fn into_owned(self) -> OwnedSpan {
+        self
+    }
+}
+
+impl Show for OwnedSpan {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Show::fmt(&self.as_span(), f)
+    }
+}
+
 #[derive(Debug)]
 pub struct Text<'a> {
     current: &'a str,
     start: &'a str,
+    lines: LineCache,
 }
 
 impl<'a> From<&'a str> for Text<'a> {
     fn from(start: &'a str) -> Text<'a> {
-        Text { start, current: start }
+        Text { start, current: start, lines: LineCache::new() }
     }
 }
 
@@ -168,12 +246,10 @@ impl<'a> Input for Text<'a> {
             let start_offset = mark;
             let end_offset = bytes_read;
 
-            let to_start_str = &self.start[..start_offset];
-            let (start_line, start_col) = line_col(to_start_str);
+            let (start_line, start_col) = self.lines.line_col(self.start, start_offset);
             let start = (start_line, start_col, start_offset);
 
-            let to_current_str = &self.start[..bytes_read];
-            let (end_line, end_col) = line_col(to_current_str);
+            let (end_line, end_col) = self.lines.line_col(self.start, bytes_read);
             let end = (end_line, end_col, bytes_read);
 
             let snippet = if end_offset <= self.start.len() {
@@ -189,15 +265,42 @@ impl<'a> Input for Text<'a> {
     }
 }
 
-fn line_col(string: &str) -> (usize, usize) {
-    if string.is_empty() {
-        return (1, 1);
+/// The line and column of the offset a context was last computed for.
+///
+/// Finding the line and column of an offset from the start of the input
+/// takes a scan of it, which would make every failure cost time linear in
+/// how far into the input it is. Instead, they're found by scanning from
+/// the last offset, which is usually close by.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct LineCache {
+    line: usize,
+    col: usize,
+    offset: usize,
+}
+
+impl LineCache {
+    pub(crate) fn new() -> Self {
+        LineCache { line: 1, col: 1, offset: 0 }
     }
 
-    let (line_count, last_line) = string.lines().enumerate().last().unwrap();
-    if string.ends_with('\n') {
-        (line_count + 2, 1)
-    } else {
-        (line_count + 1, last_line.len() + 1)
+    /// Returns the line and column of `offset` in `text`, both from 1.
+    /// Columns count bytes.
+    pub(crate) fn line_col<T: AsRef<[u8]> + ?Sized>(&mut self, text: &T, offset: usize) -> (usize, usize) {
+        let bytes = text.as_ref();
+        if offset >= self.offset {
+            for &byte in &bytes[self.offset..offset] {
+                match byte {
+                    b'\n' => { self.line += 1; self.col = 1; }
+                    _ => self.col += 1,
+                }
+            }
+        } else {
+            self.line -= bytes[offset..self.offset].iter().filter(|&&b| b == b'\n').count();
+            let line_start = bytes[..offset].iter().rposition(|&b| b == b'\n').map_or(0, |i| i + 1);
+            self.col = offset - line_start + 1;
+        }
+
+        self.offset = offset;
+        (self.line, self.col)
     }
 }
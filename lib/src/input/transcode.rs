@@ -0,0 +1,322 @@
+use crate::input::{Input, Rewind, ParserInfo, OwnedSpan};
+
+/// A source encoding that [`Transcoded`] decodes into `char`s.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Encoding {
+    /// UTF-16, little-endian.
+    Utf16Le,
+    /// UTF-16, big-endian.
+    Utf16Be,
+    /// ISO-8859-1: every byte is the code point of the same value.
+    Latin1,
+    /// A single-byte encoding that agrees with ASCII on bytes below `0x80`.
+    /// Byte `b >= 0x80` decodes to `high[b - 0x80]`.
+    SingleByte {
+        /// The name of the encoding, used in error messages.
+        name: &'static str,
+        /// The characters for bytes `0x80` through `0xFF`.
+        high: &'static [char; 128],
+    },
+}
+
+impl Encoding {
+    /// Returns the name of the encoding.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Encoding::Utf16Le => "UTF-16LE",
+            Encoding::Utf16Be => "UTF-16BE",
+            Encoding::Latin1 => "ISO-8859-1",
+            Encoding::SingleByte { name, .. } => name,
+        }
+    }
+
+    /// Decodes the character at the start of `bytes`, returning it with the
+    /// number of bytes it was encoded in. Returns `None` if `bytes` is empty
+    /// or does not start with a valid, complete character.
+    fn decode(&self, bytes: &[u8]) -> Option<(char, usize)> {
+        let unit = |i: usize| {
+            let pair = [*bytes.get(i)?, *bytes.get(i + 1)?];
+            Some(match self {
+                Encoding::Utf16Be => u16::from_be_bytes(pair),
+                _ => u16::from_le_bytes(pair),
+            })
+        };
+
+        match self {
+            Encoding::Utf16Le | Encoding::Utf16Be => {
+                let first = unit(0)?;
+                if let Some(Ok(c)) = char::decode_utf16(Some(first)).next() {
+                    return Some((c, 2));
+                }
+
+                let units = [first, unit(2)?];
+                char::decode_utf16(units.iter().copied()).next()?.ok().map(|c| (c, 4))
+            }
+            Encoding::Latin1 => bytes.first().map(|&b| (b as char, 1)),
+            Encoding::SingleByte { high, .. } => match *bytes.first()? {
+                b if b < 0x80 => Some((b as char, 1)),
+                b => Some((high[usize::from(b - 0x80)], 1)),
+            },
+        }
+    }
+}
+
+/// A text input that decodes `char` tokens from a non-UTF-8 encoding.
+///
+/// `Transcoded` lets grammars written against `char` tokens, such as those
+/// for [`Text`](crate::input::Text), run on UTF-16 and single-byte encoded
+/// sources. Since decoded text cannot be borrowed from the source, slices are
+/// `String`s and contexts are [`OwnedSpan`]s.
+///
+/// Markers and context offsets are in bytes of the _source_; context lines
+/// and columns are computed as for `Text`, over the decoded text. Lengths, as
+/// in [`Input::slice()`], are in bytes of the UTF-8 encoded _decoded_ text,
+/// so that `eat_slice(input, "GET")` matches three characters. Tokens end at
+/// the first malformed character: parsers that fail there report
+/// [`Expected::Malformed`](crate::error::Expected::Malformed).
+///
+/// ```rust
+/// use pear::input::{Pear, Transcoded};
+/// use pear::parsers::*;
+///
+/// // "é = 1" in UTF-16BE, with a byte order mark.
+/// let bytes = b"\xfe\xff\x00\xe9\x00 \x00=\x00 \x001";
+/// let mut input = Pear::from(Transcoded::utf16(bytes));
+///
+/// assert_eq!(eat(&mut input, 'é').unwrap(), 'é');
+/// assert_eq!(eat_slice(&mut input, " = ").unwrap(), " = ");
+/// assert_eq!(take_while(&mut input, |c| c.is_numeric()).unwrap(), "1");
+/// ```
+#[derive(Debug, Clone)]
+pub struct Transcoded<'a> {
+    bytes: &'a [u8],
+    encoding: Encoding,
+    start: usize,
+    offset: usize,
+    /// The last source offset a line and column were computed for, with
+    /// them, so that contexts don't decode from the start each time.
+    position: (usize, usize, usize),
+}
+
+impl<'a> Transcoded<'a> {
+    /// Creates an input that decodes `bytes` from `encoding`.
+    pub fn new(bytes: &'a [u8], encoding: Encoding) -> Self {
+        Transcoded { bytes, encoding, start: 0, offset: 0, position: (0, 1, 1) }
+    }
+
+    /// Creates an input that decodes `bytes` from UTF-16. The byte order is
+    /// detected from a leading byte order mark, which is skipped, and is
+    /// little-endian if there is none.
+    pub fn utf16(bytes: &'a [u8]) -> Self {
+        let (encoding, start) = match bytes {
+            [0xFE, 0xFF, ..] => (Encoding::Utf16Be, 2),
+            [0xFF, 0xFE, ..] => (Encoding::Utf16Le, 2),
+            _ => (Encoding::Utf16Le, 0),
+        };
+
+        Transcoded { bytes, encoding, start, offset: start, position: (start, 1, 1) }
+    }
+
+    /// Creates an input that decodes `bytes` from ISO-8859-1.
+    pub fn latin1(bytes: &'a [u8]) -> Self {
+        Transcoded::new(bytes, Encoding::Latin1)
+    }
+
+    /// Returns the encoding `self` decodes from.
+    pub fn encoding(&self) -> Encoding {
+        self.encoding
+    }
+
+    /// Decodes the character starting at source offset `offset`, returning it
+    /// with the offset just past it.
+    fn char_at(&self, offset: usize) -> Option<(char, usize)> {
+        let (c, width) = self.encoding.decode(self.bytes.get(offset..)?)?;
+        Some((c, offset + width))
+    }
+
+    /// Decodes the characters from the current position while `cond` holds,
+    /// returning them with the source offset just past the last one.
+    fn decode_while<F>(&self, mut cond: F) -> (String, usize)
+        where F: FnMut(&str, char) -> bool
+    {
+        let (mut string, mut end) = (String::new(), self.offset);
+        while let Some((c, next)) = self.char_at(end) {
+            if !cond(&string, c) { break; }
+            string.push(c);
+            end = next;
+        }
+
+        (string, end)
+    }
+
+    /// Decodes the text from the current position whose UTF-8 encoding is
+    /// exactly `n` bytes long, returning it with the source offset past it.
+    fn decode_n(&self, n: usize) -> Option<(String, usize)> {
+        let (string, end) = self.decode_while(|s, _| s.len() < n);
+        match string.len() == n {
+            true => Some((string, end)),
+            false => None,
+        }
+    }
+
+    /// Decodes the text between the source offsets `start` and `end`.
+    fn decode_range(&self, start: usize, end: usize) -> String {
+        let (mut string, mut offset) = (String::new(), start);
+        while let Some((c, next)) = self.char_at(offset).filter(|&(_, n)| n <= end) {
+            string.push(c);
+            offset = next;
+        }
+
+        string
+    }
+
+    /// Returns the line and column at the source offset `offset`, decoding
+    /// from the last offset they were computed for if it precedes `offset`,
+    /// and from the start otherwise. Lines and columns are counted as for
+    /// `Text`: columns in bytes of the decoded text.
+    fn line_col(&mut self, offset: usize) -> (usize, usize) {
+        let (mut at, mut line, mut col) = match self.position {
+            (at, line, col) if at <= offset => (at, line, col),
+            _ => (self.start, 1, 1),
+        };
+
+        while let Some((c, next)) = self.char_at(at).filter(|&(_, n)| n <= offset) {
+            match c {
+                '\n' => { line += 1; col = 1; }
+                c => col += c.len_utf8(),
+            }
+
+            at = next;
+        }
+
+        self.position = (at, line, col);
+        (line, col)
+    }
+}
+
+impl Rewind for Transcoded<'_> {
+    fn rewind_to(&mut self, marker: Self::Marker) {
+        self.offset = marker;
+    }
+}
+
+impl<'a> Input for Transcoded<'a> {
+    type Token = char;
+    type Slice = String;
+    type Many = Self::Slice;
+
+    type Marker = usize;
+    type Context = OwnedSpan;
+
+    /// Returns a copy of the current token, if there is one.
+    fn token(&mut self) -> Option<Self::Token> {
+        self.char_at(self.offset).map(|(c, _)| c)
+    }
+
+    /// Returns a copy of the current slice of size `n`, if there is one.
+    fn slice(&mut self, n: usize) -> Option<Self::Slice> {
+        self.decode_n(n).map(|(slice, _)| slice)
+    }
+
+    /// Checks if the current token fulfills `cond`.
+    fn peek<F>(&mut self, mut cond: F) -> bool
+        where F: FnMut(&Self::Token) -> bool
+    {
+        self.token().map(|t| cond(&t)).unwrap_or(false)
+    }
+
+    /// Checks if the current slice of size `n` (if any) fulfills `cond`.
+    fn peek_slice<F>(&mut self, n: usize, mut cond: F) -> bool
+        where F: FnMut(&Self::Slice) -> bool
+    {
+        self.slice(n).map(|s| cond(&s)).unwrap_or(false)
+    }
+
+    /// Checks if the current token fulfills `cond`. If so, the token is
+    /// consumed and returned. Otherwise, returns `None`.
+    fn eat<F>(&mut self, mut cond: F) -> Option<Self::Token>
+        where F: FnMut(&Self::Token) -> bool
+    {
+        let (token, next) = self.char_at(self.offset)?;
+        if cond(&token) {
+            self.offset = next;
+            Some(token)
+        } else {
+            None
+        }
+    }
+
+    /// Checks if the current slice of size `n` (if any) fulfills `cond`. If so,
+    /// the slice is consumed and returned. Otherwise, returns `None`.
+    fn eat_slice<F>(&mut self, n: usize, mut cond: F) -> Option<Self::Slice>
+        where F: FnMut(&Self::Slice) -> bool
+    {
+        let (slice, end) = self.decode_n(n)?;
+        if cond(&slice) {
+            self.offset = end;
+            Some(slice)
+        } else {
+            None
+        }
+    }
+
+    /// Takes tokens while `cond` returns true, collecting them into a
+    /// `Self::Many` and returning it.
+    fn take<F>(&mut self, mut cond: F) -> Self::Many
+        where F: FnMut(&Self::Token) -> bool
+    {
+        let (string, end) = self.decode_while(|_, c| cond(&c));
+        self.offset = end;
+        string
+    }
+
+    /// Skips tokens while `cond` returns true. Returns the number of skipped
+    /// tokens.
+    fn skip<F>(&mut self, cond: F) -> usize
+        where F: FnMut(&Self::Token) -> bool
+    {
+        self.take(cond).len()
+    }
+
+    /// Returns `true` if there are at least `n` tokens remaining.
+    fn has(&mut self, n: usize) -> bool {
+        let (mut len, mut offset) = (0, self.offset);
+        while len < n {
+            match self.char_at(offset) {
+                Some((c, next)) => { len += c.len_utf8(); offset = next; }
+                None => return false,
+            }
+        }
+
+        true
+    }
+
+    #[inline(always)]
+    fn mark(&mut self, _: &ParserInfo) -> Self::Marker {
+        self.offset
+    }
+
+    fn context(&mut self, mark: Self::Marker) -> Self::Context {
+        let cursor = self.token();
+        if self.offset == self.start {
+            let start = (1, 1, self.start);
+            return OwnedSpan { start, end: start, snippet: None, cursor };
+        }
+
+        let (start_line, start_col) = self.line_col(mark);
+        let (end_line, end_col) = self.line_col(self.offset);
+        OwnedSpan {
+            start: (start_line, start_col, mark),
+            end: (end_line, end_col, self.offset),
+            snippet: Some(self.decode_range(mark, self.offset)),
+            cursor,
+        }
+    }
+
+    fn malformed(&mut self) -> Option<(&'static str, usize)> {
+        match self.offset < self.bytes.len() && self.token().is_none() {
+            true => Some((self.encoding.name(), self.offset)),
+            false => None,
+        }
+    }
+}
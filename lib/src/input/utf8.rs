@@ -0,0 +1,217 @@
+use crate::input::{Input, Rewind, ParserInfo, Span};
+use crate::input::text::LineCache;
+
+/// A text input over bytes that are expected, but not known, to be UTF-8.
+///
+/// `Utf8Bytes` behaves like [`Text`](crate::input::Text) but decodes its
+/// `char` tokens lazily, as they are read, instead of requiring a separate
+/// validation pass. Lengths, markers, and offsets are in bytes. Tokens end at
+/// the first invalid UTF-8 sequence: parsers that fail there report
+/// [`Expected::Malformed`](crate::error::Expected::Malformed) with the byte
+/// offset of the sequence.
+///
+/// A sequence that is truncated by the end of the input is not counted as
+/// available by [`Input::has()`]. When wrapped in a
+/// [`Partial`](crate::input::Partial), such as by a
+/// [`Stream`](crate::input::Stream), parsers wait for the rest of it instead
+/// of failing.
+///
+/// ```rust
+/// use pear::input::{Pear, Utf8Bytes};
+/// use pear::error::Expected;
+/// use pear::parsers::*;
+///
+/// let mut input: Pear<Utf8Bytes<'_>> = Pear::new(&b"caf\xc3\xa9 \xff"[..]);
+/// assert_eq!(take_while(&mut input, |c| !c.is_whitespace()).unwrap(), "café");
+/// assert_eq!(eat(&mut input, ' ').unwrap(), ' ');
+///
+/// let error = eof(&mut input).unwrap_err().unwrap();
+/// assert!(matches!(error.error, Expected::Malformed { offset: 6, .. }));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Utf8Bytes<'a> {
+    bytes: &'a [u8],
+    offset: usize,
+    len: usize,
+    lines: LineCache,
+}
+
+impl<'a> From<&'a [u8]> for Utf8Bytes<'a> {
+    fn from(bytes: &'a [u8]) -> Utf8Bytes<'a> {
+        Utf8Bytes { bytes, offset: 0, len: untruncated_len(bytes), lines: LineCache::new() }
+    }
+}
+
+impl<'a> From<&'a str> for Utf8Bytes<'a> {
+    fn from(string: &'a str) -> Utf8Bytes<'a> {
+        Utf8Bytes::from(string.as_bytes())
+    }
+}
+
+/// Returns the longest prefix of `bytes` that is valid UTF-8.
+fn valid_prefix(bytes: &[u8]) -> &str {
+    match std::str::from_utf8(bytes) {
+        Ok(string) => string,
+        Err(e) => std::str::from_utf8(&bytes[..e.valid_up_to()]).unwrap(),
+    }
+}
+
+/// Returns the length of `bytes` without a trailing, truncated UTF-8 sequence.
+fn untruncated_len(bytes: &[u8]) -> usize {
+    let tail = bytes.len().saturating_sub(3);
+    let lead = bytes[tail..].iter()
+        .rposition(|b| b & 0xC0 != 0x80)
+        .map_or(bytes.len(), |i| tail + i);
+
+    match std::str::from_utf8(&bytes[lead..]) {
+        Err(e) if e.error_len().is_none() => lead + e.valid_up_to(),
+        _ => bytes.len(),
+    }
+}
+
+impl<'a> Utf8Bytes<'a> {
+    /// Decodes the character starting at `offset`, if there is a valid one.
+    fn char_at(&self, offset: usize) -> Option<char> {
+        let bytes = self.bytes.get(offset..)?;
+        valid_prefix(&bytes[..bytes.len().min(4)]).chars().next()
+    }
+
+    /// Returns the number of bytes in the run of characters starting at the
+    /// current position that satisfy `cond`.
+    fn scan<F>(&self, mut cond: F) -> usize
+        where F: FnMut(&char) -> bool
+    {
+        let mut end = self.offset;
+        while let Some(c) = self.char_at(end) {
+            if !cond(&c) { break; }
+            end += c.len_utf8();
+        }
+
+        end - self.offset
+    }
+}
+
+impl Rewind for Utf8Bytes<'_> {
+    fn rewind_to(&mut self, marker: Self::Marker) {
+        self.offset = marker;
+    }
+}
+
+impl<'a> Input for Utf8Bytes<'a> {
+    type Token = char;
+    type Slice = &'a str;
+    type Many = Self::Slice;
+
+    type Marker = usize;
+    type Context = Span<'a>;
+
+    /// Returns a copy of the current token, if there is one.
+    fn token(&mut self) -> Option<Self::Token> {
+        self.char_at(self.offset)
+    }
+
+    /// Returns a copy of the current slice of size `n`, if there is one.
+    fn slice(&mut self, n: usize) -> Option<Self::Slice> {
+        let bytes = self.bytes.get(self.offset..self.offset.checked_add(n)?)?;
+        std::str::from_utf8(bytes).ok()
+    }
+
+    /// Checks if the current token fulfills `cond`.
+    fn peek<F>(&mut self, mut cond: F) -> bool
+        where F: FnMut(&Self::Token) -> bool
+    {
+        self.token().map(|t| cond(&t)).unwrap_or(false)
+    }
+
+    /// Checks if the current slice of size `n` (if any) fulfills `cond`.
+    fn peek_slice<F>(&mut self, n: usize, mut cond: F) -> bool
+        where F: FnMut(&Self::Slice) -> bool
+    {
+        self.slice(n).map(|s| cond(&s)).unwrap_or(false)
+    }
+
+    /// Checks if the current token fulfills `cond`. If so, the token is
+    /// consumed and returned. Otherwise, returns `None`.
+    fn eat<F>(&mut self, mut cond: F) -> Option<Self::Token>
+        where F: FnMut(&Self::Token) -> bool
+    {
+        let token = self.token()?;
+        if cond(&token) {
+            self.offset += token.len_utf8();
+            Some(token)
+        } else {
+            None
+        }
+    }
+
+    /// Checks if the current slice of size `n` (if any) fulfills `cond`. If so,
+    /// the slice is consumed and returned. Otherwise, returns `None`.
+    fn eat_slice<F>(&mut self, n: usize, mut cond: F) -> Option<Self::Slice>
+        where F: FnMut(&Self::Slice) -> bool
+    {
+        let slice = self.slice(n)?;
+        if cond(&slice) {
+            self.offset += n;
+            Some(slice)
+        } else {
+            None
+        }
+    }
+
+    /// Takes tokens while `cond` returns true, collecting them into a
+    /// `Self::Many` and returning it.
+    fn take<F>(&mut self, cond: F) -> Self::Many
+        where F: FnMut(&Self::Token) -> bool
+    {
+        let start = self.offset;
+        self.offset += self.scan(cond);
+        valid_prefix(&self.bytes[start..self.offset])
+    }
+
+    /// Skips tokens while `cond` returns true. Returns the number of skipped
+    /// tokens.
+    fn skip<F>(&mut self, cond: F) -> usize
+        where F: FnMut(&Self::Token) -> bool
+    {
+        let skipped = self.scan(cond);
+        self.offset += skipped;
+        skipped
+    }
+
+    /// Returns `true` if there are at least `n` tokens remaining.
+    fn has(&mut self, n: usize) -> bool {
+        self.len - self.offset >= n
+    }
+
+    #[inline(always)]
+    fn mark(&mut self, _: &ParserInfo) -> Self::Marker {
+        self.offset
+    }
+
+    fn context(&mut self, mark: Self::Marker) -> Self::Context {
+        let cursor = self.token();
+
+        if self.offset == 0 {
+            return Span { start: (1, 1, 0), end: (1, 1, 0), snippet: None, cursor };
+        }
+
+        // Every consumed byte was decoded, so the read bytes are valid.
+        let snippet = valid_prefix(&self.bytes[mark..self.offset]);
+        let end = mark + snippet.len();
+        let (start_line, start_col) = self.lines.line_col(self.bytes, mark);
+        let (end_line, end_col) = self.lines.line_col(self.bytes, end);
+        Span {
+            start: (start_line, start_col, mark),
+            end: (end_line, end_col, end),
+            snippet: Some(snippet),
+            cursor,
+        }
+    }
+
+    fn malformed(&mut self) -> Option<(&'static str, usize)> {
+        match self.offset < self.bytes.len() && self.token().is_none() {
+            true => Some(("UTF-8", self.offset)),
+            false => None,
+        }
+    }
+}
@@ -8,7 +8,12 @@ pub mod result;
 pub mod error;
 pub mod parsers;
 pub mod combinators;
+pub mod catalog;
+#[cfg(feature = "lsp-types")] pub mod lsp;
+#[cfg(any(feature = "codespan-reporting", feature = "ariadne", feature = "miette"))]
+pub mod report;
 
 mod expected;
+#[cfg(feature = "serde")] mod serialization;
 
 #[doc(hidden)] pub mod debug;
@@ -0,0 +1,186 @@
+//! Conversion of parse errors into Language Server Protocol diagnostics.
+//!
+//! A [`Document`] pairs the text a [`Text`](crate::input::Text) parser ran on
+//! with the URI of the file it came from, and converts the errors of that
+//! parse into [`lsp_types`] values:
+//!
+//!   * [`Document::diagnostic()`] converts an error into a `Diagnostic`. The
+//!     error's code, notes, help, labels, and the named parsers on its stack
+//!     become the diagnostic's code, message, and related information.
+//!   * [`Document::diagnostics()`] converts a list of errors, such as those
+//!     collected while recovering from failures.
+//!   * [`Document::code_actions()`] converts an error's suggestions into
+//!     quick-fix `CodeAction`s.
+//!
+//! LSP positions count columns in UTF-16 code units, while [`Span`] counts
+//! them in bytes. `Document` converts between the two using the text.
+//!
+//! Enabled by the `lsp-types` feature.
+//!
+//! ```rust
+//! use pear::input::{Pear, Text, Span};
+//! use pear::macros::parser;
+//! use pear::parsers::*;
+//! use pear::lsp::Document;
+//!
+//! #[parser]
+//! fn greeting<'a>(input: &mut Pear<Text<'a>>) -> pear::input::Result<&'a str, Text<'a>> {
+//!     eat_slice("¡hola")?;
+//!     eat(' ')?;
+//!     eat_slice("mundo")?
+//! }
+//!
+//! let text = "¡hola, mundo";
+//! let error = greeting(&mut Pear::new(text)).unwrap_err().unwrap();
+//!
+//! let document = Document::new("file:///greeting.txt".parse().unwrap(), text);
+//! let diagnostic = document.diagnostic(&error);
+//! assert_eq!(diagnostic.range.start.character, 5);
+//! assert_eq!(diagnostic.message, "expected token ' ' but found ','");
+//! ```
+
+use std::collections::HashMap;
+use std::fmt::Display;
+
+use lsp_types::{Uri, Position, Range, Location, NumberOrString, TextEdit, WorkspaceEdit};
+use lsp_types::{Diagnostic, DiagnosticSeverity, DiagnosticRelatedInformation};
+use lsp_types::{CodeAction, CodeActionKind};
+
+use crate::error::ParseError;
+use crate::input::Span;
+
+/// The text of a document and its URI, for converting errors from parsing
+/// the text into LSP values.
+#[derive(Debug, Clone)]
+pub struct Document<'a> {
+    uri: Uri,
+    text: &'a str,
+    source: Option<String>,
+    severity: DiagnosticSeverity,
+}
+
+impl<'a> Document<'a> {
+    /// Creates a document for `text`, the entire input that was parsed,
+    /// located at `uri`. Diagnostics have severity `ERROR` and no source.
+    pub fn new(uri: Uri, text: &'a str) -> Self {
+        Document { uri, text, source: None, severity: DiagnosticSeverity::ERROR }
+    }
+
+    /// Sets the `source` of diagnostics, typically the name of the language.
+    pub fn with_source<S: Into<String>>(mut self, source: S) -> Self {
+        self.source = Some(source.into());
+        self
+    }
+
+    /// Sets the severity of diagnostics.
+    pub fn with_severity(mut self, severity: DiagnosticSeverity) -> Self {
+        self.severity = severity;
+        self
+    }
+
+    /// The URI of the document.
+    pub fn uri(&self) -> &Uri {
+        &self.uri
+    }
+
+    /// Converts a `(line, column, offset)` position of a [`Span`] into an LSP
+    /// position, with a column in UTF-16 code units.
+    pub fn position(&self, (line, column, offset): (usize, usize, usize)) -> Position {
+        let line_start = offset.saturating_sub(column.saturating_sub(1));
+        let character = match self.text.get(line_start..offset) {
+            Some(prefix) => prefix.encode_utf16().count(),
+            None => column.saturating_sub(1),
+        };
+
+        Position::new(line.saturating_sub(1) as u32, character as u32)
+    }
+
+    /// Converts a span into an LSP range.
+    pub fn range(&self, span: &Span<'_>) -> Range {
+        Range::new(self.position(span.start), self.position(span.end))
+    }
+
+    fn location(&self, span: &Span<'_>) -> Location {
+        Location::new(self.uri.clone(), self.range(span))
+    }
+
+    /// Converts `error` into a diagnostic.
+    ///
+    /// The message is the error followed by its notes and help. The related
+    /// information lists the error's labels, then the contexts of the named
+    /// parsers on its stack, innermost first; the built-in (`raw`) parsers
+    /// are omitted.
+    pub fn diagnostic<E: Display>(&self, error: &ParseError<Span<'_>, E>) -> Diagnostic {
+        let mut message = error.error.to_string();
+        for note in error.notes() {
+            message.push_str("\nnote: ");
+            message.push_str(note);
+        }
+
+        if let Some(help) = error.help() {
+            message.push_str("\nhelp: ");
+            message.push_str(help);
+        }
+
+        let labels = error.labels().iter()
+            .map(|label| (&label.context, label.message.to_string()));
+
+        let stack = error.stack.iter()
+            .filter(|info| !info.parser.raw)
+            .map(|info| (&info.context, match info.count {
+                1 => format!("while parsing `{}`", info.parser.name),
+                n => format!("while parsing `{}` ×{}", info.parser.name, n),
+            }));
+
+        let related: Vec<_> = labels.chain(stack)
+            .map(|(context, message)| DiagnosticRelatedInformation {
+                location: self.location(context),
+                message
+            })
+            .collect();
+
+        Diagnostic {
+            range: self.range(&error.info.context),
+            severity: Some(self.severity),
+            code: error.code().map(|code| NumberOrString::String(code.into())),
+            source: self.source.clone(),
+            message,
+            related_information: Some(related).filter(|r| !r.is_empty()),
+            ..Diagnostic::default()
+        }
+    }
+
+    /// Converts each of `errors` into a diagnostic.
+    pub fn diagnostics<'e, 's: 'e, E, I>(&self, errors: I) -> Vec<Diagnostic>
+        where E: Display + 'e, I: IntoIterator<Item = &'e ParseError<Span<'s>, E>>
+    {
+        errors.into_iter().map(|error| self.diagnostic(error)).collect()
+    }
+
+    /// Converts the suggestions of `error` into quick-fix code actions, each
+    /// replacing the suggestion's context with its replacement. A lone
+    /// suggestion is marked as preferred.
+    #[allow(clippy::mutable_key_type)]
+    pub fn code_actions<E: Display>(&self, error: &ParseError<Span<'_>, E>) -> Vec<CodeAction> {
+        let suggestions = error.suggestions();
+        if suggestions.is_empty() {
+            return vec![];
+        }
+
+        let diagnostic = self.diagnostic(error);
+        suggestions.iter()
+            .map(|suggestion| {
+                let edit = TextEdit::new(self.range(&suggestion.context), suggestion.replacement.to_string());
+                let changes = HashMap::from([(self.uri.clone(), vec![edit])]);
+                CodeAction {
+                    title: format!("replace with `{}`", suggestion.replacement),
+                    kind: Some(CodeActionKind::QUICKFIX),
+                    diagnostics: Some(vec![diagnostic.clone()]),
+                    edit: Some(WorkspaceEdit::new(changes)),
+                    is_preferred: Some(suggestions.len() == 1),
+                    ..CodeAction::default()
+                }
+            })
+            .collect()
+    }
+}
@@ -3,6 +3,7 @@
 //!
 //!   * [`parse_declare!`](#parse_declare)
 //!   * [`parse_error!`](#parse_error)
+//!   * [`parse_commit!`](#parse_commit)
 //!   * [`impl_show_with!`](#impl_show_with)
 //!
 //! [`Input`]: crate::input::Input
@@ -20,11 +21,14 @@ pub use crate::{parse, parse_declare, parse_error, parse_try, is_parse_debug};
 #[doc(inline)]
 pub use crate::{parse_current_marker, parse_last_marker, parse_mark, parse_context};
 #[doc(inline)]
+pub use crate::parse_commit;
+#[doc(inline)]
 pub use crate::impl_show_with;
 
 /// Runs the parser with the given name and input, then [`parsers::eof()`].
 ///
-/// Returns the combined result.
+/// Returns the combined result. Its error is the `ParseError` of the failure,
+/// as the failure of a parser run at the top level is reported.
 ///
 /// Syntax:
 ///
@@ -39,20 +43,28 @@ pub use crate::impl_show_with;
 macro_rules! parse {
     ($parser:ident : &mut $e:expr) => ({
         let input = &mut $e;
-        (move || {
+        #[allow(clippy::result_large_err)]
+        let mut parse = || {
             let result = $parser(input)?;
             $crate::parsers::eof(input).map_err(|e| e.into())?;
             $crate::result::AsResult::as_result(result)
-        })()
+        };
+
+        let info = $crate::input::ParserInfo { name: stringify!($parser), raw: false };
+        parse().map_err(|failure| input.report(info, failure))
     });
     ($parser:ident : $e:expr) => (parse!($parser(): $e));
     ($parser:ident ($($x:expr),*) : $e:expr) => ({
         let mut input: $crate::input::Pear<_> = $e.into();
-        (move || {
+        #[allow(clippy::result_large_err)]
+        let mut parse = || {
             let result = $parser(&mut input $(, $x)*)?;
             $crate::parsers::eof(&mut input).map_err(|e| e.into())?;
             $crate::result::AsResult::as_result(result)
-        })()
+        };
+
+        let info = $crate::input::ParserInfo { name: stringify!($parser), raw: false };
+        parse().map_err(|failure| input.report(info, failure))
     })
 }
 
@@ -93,14 +105,98 @@ macro_rules! iformat {
 }
 
 /// Returns an `Err(ParseError::new($e))`. Can used like `format!` as well.
+///
+/// The error `$e` can be a value of a custom error type, which `?` converts
+/// into the parser's error type via `From`. When used like `format!`, the
+/// error is an [`Expected::Other`](crate::error::Expected::Other).
+///
+/// A failure that won't be reported, such as one in a `switch!` arm that
+/// isn't taken, is signaled cheaply with [`Failure::Elided`]: the error isn't
+/// evaluated, no context is taken, and, when used like `format!`, the message
+/// isn't formatted. See [`Result`](crate::result::Result).
+///
+/// [`Failure::Elided`]: crate::error::Failure::Elided
+///
+/// [Diagnostic metadata](crate::error::Diagnostic) can follow the error after
+/// a `;` as a comma-separated list of `code = EXPR`, `help = EXPR`, `note =
+/// EXPR`, and `label(CONTEXT) = EXPR`, where each `EXPR` is a string and
+/// `CONTEXT` is a context of the input, as returned by `parse_context!()`:
+///
+/// ```rust
+/// use pear::input::{Pear, Text, Result, Input as _};
+/// use pear::macros::{parser, parse_error, parse_context};
+/// use pear::parsers::*;
+///
+/// #[parser]
+/// fn sections<'a>(input: &mut Pear<Text<'a>>) -> Result<Vec<&'a str>, Text<'a>> {
+///     let mut names: Vec<(&str, _)> = vec![];
+///     while input.token().is_some() {
+///         eat('[')?;
+///         let name = take_some_while(|c| c.is_alphabetic())?;
+///         eat(']')?;
+///         if let Some((_, first)) = names.iter().find(|(n, _)| *n == name) {
+///             parse_error!("duplicate section `{}`", name;
+///                 code = "E0042",
+///                 label(*first) = "first defined here",
+///                 help = "rename one of the sections")?;
+///         }
+///
+///         names.push((name, parse_context!()));
+///         skip_while(|c| *c == '\n')?;
+///     }
+///
+///     names.into_iter().map(|(name, _)| name).collect::<Vec<_>>()
+/// }
+///
+/// let error = sections(&mut Pear::new("[a]\n[b]\n[a]")).unwrap_err().unwrap();
+/// assert_eq!(error.code(), Some("E0042"));
+/// assert_eq!(error.labels()[0].context.start, (1, 1, 0));
+/// assert!(error.to_string().starts_with("E0042: duplicate section `a`"));
+/// ```
 #[macro_export]
 macro_rules! parse_error {
     ([$info:expr; $input:expr; $marker:expr; $T:ty] $err:expr) => ({
-        let context = $crate::parse_context!([$info; $input; $marker; $T]);
-        Err($crate::error::ParseError::new(*$info, $err, context))
+        match $input.emit_error {
+            true => {
+                let context = $crate::parse_context!([$info; $input; $marker; $T]);
+                Err($crate::error::Failure::Error($crate::error::ParseError::new(*$info, $err, context)))
+            }
+            false => Err($crate::error::Failure::Elided),
+        }
     });
+    ([$info:expr; $input:expr; $marker:expr; $T:ty] $err:expr; $($meta:tt)+) => ({
+        match $input.emit_error {
+            true => {
+                let context = $crate::parse_context!([$info; $input; $marker; $T]);
+                let error = $crate::error::ParseError::new(*$info, $err, context);
+                Err($crate::error::Failure::Error($crate::_parse_error_meta!(error; $($meta)+)))
+            }
+            false => Err($crate::error::Failure::Elided),
+        }
+    });
+    ([$n:expr; $i:expr; $m:expr; $T:ty] $fmt:literal, $($arg:expr),+; $($meta:tt)+) => {
+        parse_error!([$n; $i; $m; $T] $crate::error::Expected::<_, _>::from($crate::iformat!($fmt, $($arg),+)); $($meta)+)
+    };
     ([$n:expr; $i:expr; $m:expr; $T:ty] $fmt:expr, $($arg:tt)*) => {
-        parse_error!([$n; $i; $m; $T] $crate::iformat!($fmt, $($arg)*))
+        parse_error!([$n; $i; $m; $T] $crate::error::Expected::<_, _>::from($crate::iformat!($fmt, $($arg)*)))
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! _parse_error_meta {
+    ($e:expr;) => ($e);
+    ($e:expr; code = $v:expr $(, $($rest:tt)*)?) => {
+        $crate::_parse_error_meta!($e.with_code($v); $($($rest)*)?)
+    };
+    ($e:expr; help = $v:expr $(, $($rest:tt)*)?) => {
+        $crate::_parse_error_meta!($e.with_help($v); $($($rest)*)?)
+    };
+    ($e:expr; note = $v:expr $(, $($rest:tt)*)?) => {
+        $crate::_parse_error_meta!($e.with_note($v); $($($rest)*)?)
+    };
+    ($e:expr; label($context:expr) = $v:expr $(, $($rest:tt)*)?) => {
+        $crate::_parse_error_meta!($e.with_label($context, $v); $($($rest)*)?)
     };
 }
 
@@ -140,6 +236,46 @@ macro_rules! parse_context {
     );
 }
 
+/// Commits the current parser: if it fails from this point on, the failure is
+/// fatal and is not backtracked. `switch!`, `parse_try!` and `try_collect()`
+/// propagate a fatal failure instead of trying alternatives. Because the error
+/// will be reported, errors are no longer elided once committed.
+///
+/// Invoked with no arguments: `parse_commit!()`
+///
+/// ```rust
+/// use pear::input::{Pear, Text, Result};
+/// use pear::macros::{parser, switch, parse_commit};
+/// use pear::parsers::*;
+///
+/// #[parser]
+/// fn tuple<'a>(input: &mut Pear<Text<'a>>) -> Result<(char, char), Text<'a>> {
+///     eat('(')?;
+///     parse_commit!();
+///     let pair = (eat_any()?, eat_any()?);
+///     eat(')')?;
+///     pair
+/// }
+///
+/// #[parser]
+/// fn value<'a>(input: &mut Pear<Text<'a>>) -> Result<Option<(char, char)>, Text<'a>> {
+///     switch! {
+///         t@tuple() => Some(t),
+///         _ => None,
+///     }
+/// }
+///
+/// assert_eq!(value(&mut Pear::new("(ab)")).unwrap(), Some(('a', 'b')));
+/// assert_eq!(value(&mut Pear::new("ab")).unwrap(), None);
+/// assert!(value(&mut Pear::new("(ab")).is_err());
+/// ```
+#[macro_export]
+macro_rules! parse_commit {
+    ([$n:expr; $input:expr; $marker:expr; $T:ty]) => {{
+        $input.commit();
+    }}
+}
+
 /// Runs a parser returning `Some` if it succeeds or `None` otherwise.
 ///
 /// Take a single parser expression as input. Without additional arguments,
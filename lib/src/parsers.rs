@@ -1,5 +1,5 @@
 use crate::error::Expected;
-use crate::input::{Input, Pear, Length, Token, Slice, Show, Result, Rewind};
+use crate::input::{Input, Pear, Length, Token, Slice, Show, Result, Rewind, Bits};
 use crate::combinators::ok;
 use crate::macros::parser;
 
@@ -10,73 +10,86 @@ use crate::macros::parser;
 // //   - escaped string, with some way to configure escapes
 
 #[inline]
-fn expected_token<T, I>(
-    input: &mut Pear<I>,
+fn expected_token<T, I, U>(
+    input: &mut Pear<I, U>,
     token: Option<T>
 ) -> Expected<I::Token, I::Slice>
     where T: Token<I>, I: Input
 {
-    if input.emit_error {
-        // TODO: Have some way to test this is being called minimally.
-        // println!("Expected token.");
-        let string = token.map(|t| iformat!("{}", &t as &dyn Show));
-        Expected::Token(string, input.token())
-    } else {
-        Expected::Elided
+    if input.is_partial() && !input.has(1) {
+        return Expected::Incomplete { needed: Some(1) };
     }
+
+    if let Some((encoding, offset)) = input.malformed() {
+        return Expected::Malformed { encoding, offset };
+    }
+
+    let string = token.map(|t| iformat!("{}", &t as &dyn Show));
+    Expected::Token(string, input.token())
 }
 
 #[inline]
-fn expected_slice<S, I>(
-    input: &mut Pear<I>,
-    slice: S
+fn expected_slice<S, I, U>(
+    input: &mut Pear<I, U>,
+    slice: S,
+    ignore_case: bool
 ) -> Expected<I::Token, I::Slice>
-    where S: Slice<I>, I: Input
+    where S: Show + Length, I: Input
 {
-    if input.emit_error {
-        // TODO: Have some way to test this is being called minimally.
-        // println!("Expected slice.");
-        let string = iformat!("{}", &slice as &dyn Show);
-        Expected::Slice(Some(string), input.slice(slice.len()))
-    } else {
-        Expected::Elided
+    if input.is_partial() && !input.has(slice.len()) {
+        let available = (0..slice.len()).rev().find(|&n| input.has(n)).unwrap_or(0);
+        return Expected::Incomplete { needed: Some(slice.len() - available) };
     }
+
+    if let Some((encoding, offset)) = input.malformed() {
+        return Expected::Malformed { encoding, offset };
+    }
+
+    let string = match ignore_case || input.ignores_case() {
+        true => iformat!("{} (case-insensitive)", &slice as &dyn Show),
+        false => iformat!("{}", &slice as &dyn Show),
+    };
+
+    Expected::Slice(Some(string), input.slice(slice.len()))
 }
 
 /// Eats the current token if it is `token`.
 #[parser(raw)]
-pub fn eat<I, T>(input: &mut Pear<I>, token: T) -> Result<I::Token, I>
+pub fn eat<I, T, U>(input: &mut Pear<I, U>, token: T) -> Result<I::Token, I>
     where I: Input, T: Token<I>
 {
     match input.eat(|t| &token == t) {
         Some(token) => Ok(token),
-        None => return parse_error!(expected_token(input, Some(token)))
+        None => {
+            input.expected_literal(&token);
+            return parse_error!(expected_token(input, Some(token)))
+        }
     }
 }
 
 /// Eats the token `token` if `cond` holds on the current token.
 #[parser(raw)]
-pub fn eat_if<I, F>(input: &mut Pear<I>, cond: F) -> Result<I::Token, I>
+pub fn eat_if<I, F, U>(input: &mut Pear<I, U>, cond: F) -> Result<I::Token, I>
     where I: Input, F: FnMut(&I::Token) -> bool
 {
     match input.eat(cond) {
         Some(token) => Ok(token),
-        None => parse_error!(expected_token::<I::Token, _>(input, None))
+        None => parse_error!(expected_token::<I::Token, _, _>(input, None))
     }
 }
 
 /// Eats the current token unconditionally. Fails if there are no tokens.
 #[parser(raw)]
-pub fn eat_any<I: Input>(input: &mut Pear<I>) -> Result<I::Token, I> {
+pub fn eat_any<I: Input, U>(input: &mut Pear<I, U>) -> Result<I::Token, I> {
     match input.eat(|_| true) {
         Some(token) => Ok(token),
-        None => return parse_error!(Expected::Token(None, None))
+        None => return parse_error!(expected_token::<I::Token, _, _>(input, None))
     }
 }
 
 /// Skips the current token unconditionally. Fails if there are no tokens.
 #[parser(raw)]
-pub fn skip_any<I: Input>(input: &mut Pear<I>) -> Result<(), I> {
+pub fn skip_any<I: Input, U>(input: &mut Pear<I, U>) -> Result<(), I> {
     let mut skipped = false;
     input.skip(|_| {
         if !skipped {
@@ -89,24 +102,47 @@ pub fn skip_any<I: Input>(input: &mut Pear<I>) -> Result<(), I> {
 
     match skipped {
         true => Ok(()),
-        false => return parse_error!(Expected::Token(None, None)),
+        false => return parse_error!(expected_token::<I::Token, _, _>(input, None)),
     }
 }
 
 /// Eats the current slice if it is `slice`.
 #[parser(raw)]
-pub fn eat_slice<I, S>(input: &mut Pear<I>, slice: S) -> Result<I::Slice, I>
+pub fn eat_slice<I, S, U>(input: &mut Pear<I, U>, slice: S) -> Result<I::Slice, I>
     where I: Input, S: Slice<I>
 {
     match input.eat_slice(slice.len(), |s| &slice == s) {
         Some(slice) => Ok(slice),
-        None => return parse_error!(expected_slice(input, slice))
+        None => {
+            input.expected_literal(&slice);
+            return parse_error!(expected_slice(input, slice, false))
+        }
+    }
+}
+
+/// Eats the current slice if it is `slice`, ignoring ASCII case. Returns the
+/// slice as it appears in the input.
+///
+/// Only ASCII letters are folded. To match ignoring Unicode case, wrap the
+/// input in [`CaseFold::unicode`](crate::input::CaseFold::unicode) and use
+/// [`eat_slice`].
+#[parser(raw)]
+pub fn eat_slice_ignore_case<I, S, U>(input: &mut Pear<I, U>, slice: S) -> Result<I::Slice, I>
+    where I: Input, I::Slice: AsRef<[u8]>, S: Show + Length + AsRef<[u8]>
+{
+    let bytes = slice.as_ref();
+    match input.eat_slice(slice.len(), |s| bytes.eq_ignore_ascii_case(s.as_ref())) {
+        Some(slice) => Ok(slice),
+        None => {
+            input.expected_literal(&slice);
+            parse_error!(expected_slice(input, slice, true))
+        }
     }
 }
 
 /// Succeeds if the current token is `token`.
 #[parser(raw)]
-pub fn peek<I, T>(input: &mut Pear<I>, token: T) -> Result<(), I>
+pub fn peek<I, T, U>(input: &mut Pear<I, U>, token: T) -> Result<(), I>
     where I: Input, T: Token<I>
 {
     match input.peek(|t| &token == t) {
@@ -117,49 +153,62 @@ pub fn peek<I, T>(input: &mut Pear<I>, token: T) -> Result<(), I>
 
 /// Succeeds if `cond` holds for the current token.
 #[parser(raw)]
-pub fn peek_if_copy<I, F>(input: &mut Pear<I>, cond: F) -> Result<I::Token, I>
+pub fn peek_if_copy<I, F, U>(input: &mut Pear<I, U>, cond: F) -> Result<I::Token, I>
     where I: Input, F: FnMut(&I::Token) -> bool
 {
     match input.peek(cond) {
         true => Ok(input.token().unwrap()),
-        false => parse_error!(expected_token::<I::Token, _>(input, None))
+        false => parse_error!(expected_token::<I::Token, _, _>(input, None))
     }
 }
 
 /// Succeeds if `cond` holds for the current token.
 #[parser(raw)]
-pub fn peek_if<I, F>(input: &mut Pear<I>, cond: F) -> Result<(), I>
+pub fn peek_if<I, F, U>(input: &mut Pear<I, U>, cond: F) -> Result<(), I>
     where I: Input, F: FnMut(&I::Token) -> bool
 {
     match input.peek(cond) {
         true => Ok(()),
-        false => parse_error!(expected_token::<I::Token, _>(input, None))
+        false => parse_error!(expected_token::<I::Token, _, _>(input, None))
     }
 }
 
 /// Succeeds if the current slice is `slice`.
 #[parser(raw)]
-pub fn peek_slice<I, S>(input: &mut Pear<I>, slice: S) -> Result<(), I>
+pub fn peek_slice<I, S, U>(input: &mut Pear<I, U>, slice: S) -> Result<(), I>
     where I: Input, S: Slice<I>
 {
     match input.peek_slice(slice.len(), |s| &slice == s) {
         true => Ok(()),
-        false => return parse_error!(expected_slice(input, slice)),
+        false => return parse_error!(expected_slice(input, slice, false)),
+    }
+}
+
+/// Succeeds if the current slice is `slice`, ignoring ASCII case. As with
+/// [`eat_slice_ignore_case`], only ASCII letters are folded.
+#[parser(raw)]
+pub fn peek_slice_ignore_case<I, S, U>(input: &mut Pear<I, U>, slice: S) -> Result<(), I>
+    where I: Input, I::Slice: AsRef<[u8]>, S: Show + Length + AsRef<[u8]>
+{
+    let bytes = slice.as_ref();
+    match input.peek_slice(slice.len(), |s| bytes.eq_ignore_ascii_case(s.as_ref())) {
+        true => Ok(()),
+        false => parse_error!(expected_slice(input, slice, true)),
     }
 }
 
 /// Returns the current token.
 #[parser(raw)]
-pub fn peek_any<I: Input>(input: &mut Pear<I>) -> Result<I::Token, I> {
+pub fn peek_any<I: Input, U>(input: &mut Pear<I, U>) -> Result<I::Token, I> {
     match input.token() {
         Some(peeked) => Ok(peeked),
-        None => return parse_error!(Expected::Token(None, None)),
+        None => return parse_error!(expected_token::<I::Token, _, _>(input, None)),
     }
 }
 
 /// Skips tokens while `cond` matches.
 #[parser(raw)]
-pub fn skip_while<I, F>(input: &mut Pear<I>, cond: F) -> Result<usize, I>
+pub fn skip_while<I, F, U>(input: &mut Pear<I, U>, cond: F) -> Result<usize, I>
     where I: Input, F: FnMut(&I::Token) -> bool
 {
     Ok(input.skip(cond))
@@ -168,7 +217,7 @@ pub fn skip_while<I, F>(input: &mut Pear<I>, cond: F) -> Result<usize, I>
 /// Consumes tokens while `cond` matches and returns them. Succeeds even if no
 /// tokens match.
 #[parser(raw)]
-pub fn take_while<I, F>(input: &mut Pear<I>, cond: F) -> Result<I::Many, I>
+pub fn take_while<I, F, U>(input: &mut Pear<I, U>, cond: F) -> Result<I::Many, I>
     where I: Input, F: FnMut(&I::Token) -> bool
 {
     Ok(input.take(cond))
@@ -178,7 +227,7 @@ pub fn take_while<I, F>(input: &mut Pear<I>, cond: F) -> Result<I::Many, I>
 /// beginning at a length of `0` and ending when `cond` fails. Returns the slice
 /// between `0` and `cond` failing. Errors if no such slice exists.
 #[parser(raw)]
-pub fn take_while_slice<I, F>(input: &mut Pear<I>, mut f: F) -> Result<I::Slice, I>
+pub fn take_while_slice<I, F, U>(input: &mut Pear<I, U>, mut f: F) -> Result<I::Slice, I>
     where I: Input, F: FnMut(&I::Slice) -> bool
 {
     let mut len = 0;
@@ -212,7 +261,7 @@ pub fn take_while_slice<I, F>(input: &mut Pear<I>, mut f: F) -> Result<I::Slice,
 /// Always succeeds. If no tokens match, the result will be empty. If there are
 /// fewer than `n` tokens, takes all tokens and returns them.
 #[parser(raw)]
-pub fn take_while_window<I, F>(input: &mut Pear<I>, n: usize, mut f: F) -> Result<I::Many, I>
+pub fn take_while_window<I, F, U>(input: &mut Pear<I, U>, n: usize, mut f: F) -> Result<I::Many, I>
     where I: Input + Rewind, F: FnMut(&I::Slice) -> bool
 {
     if !input.has(n) {
@@ -247,7 +296,7 @@ pub fn take_while_window<I, F>(input: &mut Pear<I>, n: usize, mut f: F) -> Resul
 /// returns them. Fails if there no tokens match, otherwise returns all of the
 /// tokens before the first failure.
 #[parser(raw)]
-pub fn take_some_while_window<I, F>(input: &mut Pear<I>, n: usize, f: F) -> Result<I::Many, I>
+pub fn take_some_while_window<I, F, U>(input: &mut Pear<I, U>, n: usize, f: F) -> Result<I::Many, I>
     where I: Input + Rewind, F: FnMut(&I::Slice) -> bool
 {
     let result = take_while_window(n, f)?;
@@ -262,7 +311,7 @@ pub fn take_some_while_window<I, F>(input: &mut Pear<I>, n: usize, f: F) -> Resu
 /// returns them. Fails if there aren't at least `n` tokens, otherwise always
 /// otherwise always succeeds. If no tokens match, the result will be empty.
 #[parser(raw)]
-pub fn take_while_some_window<I, F>(input: &mut Pear<I>, n: usize, f: F) -> Result<I::Many, I>
+pub fn take_while_some_window<I, F, U>(input: &mut Pear<I, U>, n: usize, f: F) -> Result<I::Many, I>
     where I: Input + Rewind, F: FnMut(&I::Slice) -> bool
 {
     if !input.has(n) {
@@ -276,7 +325,7 @@ pub fn take_while_some_window<I, F>(input: &mut Pear<I>, n: usize, f: F) -> Resu
 /// returns them. Fails if there aren't at least `n` tokens or if no tokens
 /// match, otherwise returns all of the tokens before the first failure.
 #[parser(raw)]
-pub fn take_some_while_some_window<I, F>(input: &mut Pear<I>, n: usize, f: F) -> Result<I::Many, I>
+pub fn take_some_while_some_window<I, F, U>(input: &mut Pear<I, U>, n: usize, f: F) -> Result<I::Many, I>
     where I: Input + Rewind, F: FnMut(&I::Slice) -> bool
 {
     if !input.has(n) {
@@ -289,7 +338,7 @@ pub fn take_some_while_some_window<I, F>(input: &mut Pear<I>, n: usize, f: F) ->
 /// Consumes tokens while `cond` matches on a window of tokens of size `n` and
 /// returns them. Succeeds even if no tokens match.
 #[parser(raw)]
-pub fn take_until_slice<I, S>(input: &mut Pear<I>, slice: S) -> Result<I::Many, I>
+pub fn take_until_slice<I, S, U>(input: &mut Pear<I, U>, slice: S) -> Result<I::Many, I>
     where I: Input + Rewind, S: Slice<I>
 {
     take_while_window(input, slice.len(), |s| &slice != s)
@@ -298,12 +347,12 @@ pub fn take_until_slice<I, S>(input: &mut Pear<I>, slice: S) -> Result<I::Many,
 /// Consumes tokens while `cond` matches and returns them. Succeeds only if at
 /// least one token matched `cond`.
 #[parser(raw)]
-pub fn take_some_while<I, F>(input: &mut Pear<I>, cond: F) -> Result<I::Many, I>
+pub fn take_some_while<I, F, U>(input: &mut Pear<I, U>, cond: F) -> Result<I::Many, I>
     where I: Input, F: FnMut(&I::Token) -> bool
 {
     let value = input.take(cond);
     if value.len() == 0 {
-        return parse_error!(Expected::Token(None, None));
+        return parse_error!(expected_token::<I::Token, _, _>(input, None));
     }
 
     Ok(value)
@@ -312,8 +361,8 @@ pub fn take_some_while<I, F>(input: &mut Pear<I>, cond: F) -> Result<I::Many, I>
 /// Consumes tokens while `cond` matches and the token is not `until`. Succeeds
 /// even if no tokens match.
 #[parser(raw)]
-pub fn take_while_until<I, T, F>(
-    input: &mut Pear<I>,
+pub fn take_while_until<I, T, F, U>(
+    input: &mut Pear<I, U>,
     mut cond: F,
     until: T,
 ) -> Result<I::Many, I>
@@ -327,8 +376,8 @@ pub fn take_while_until<I, T, F>(
 /// Consumes tokens while `cond` matches and the token is not `until`. Succeeds
 /// only if at least one token matched `cond`.
 #[parser(raw)]
-pub fn take_some_while_until<I, T, F>(
-    input: &mut Pear<I>,
+pub fn take_some_while_until<I, T, F, U>(
+    input: &mut Pear<I, U>,
     mut cond: F,
     until: T,
 ) -> Result<I::Many, I>
@@ -341,14 +390,14 @@ pub fn take_some_while_until<I, T, F>(
 
 /// Takes at most `n` tokens.
 #[parser(raw)]
-pub fn take_n<I: Input>(input: &mut Pear<I>, n: usize) -> Result<I::Many, I> {
+pub fn take_n<I: Input, U>(input: &mut Pear<I, U>, n: usize) -> Result<I::Many, I> {
     let mut i = 0;
     Ok(input.take(|_| { let c = i < n; i += 1; c }))
 }
 
 /// Takes at most `n` tokens as long as `cond` holds.
 #[parser(raw)]
-pub fn take_n_while<I, F>(input: &mut Pear<I>, n: usize, mut cond: F) -> Result<I::Many, I>
+pub fn take_n_while<I, F, U>(input: &mut Pear<I, U>, n: usize, mut cond: F) -> Result<I::Many, I>
     where I: Input, F: FnMut(&I::Token) -> bool
 {
     let mut i = 0;
@@ -357,13 +406,13 @@ pub fn take_n_while<I, F>(input: &mut Pear<I>, n: usize, mut cond: F) -> Result<
 
 /// Take exactly `n` tokens, ensuring `cond` holds on all `n`.
 #[parser(raw)]
-pub fn take_n_if<I, F>(input: &mut Pear<I>, n: usize, mut cond: F) -> Result<I::Many, I>
+pub fn take_n_if<I, F, U>(input: &mut Pear<I, U>, n: usize, mut cond: F) -> Result<I::Many, I>
     where I: Input, F: FnMut(&I::Token) -> bool
 {
     let mut i = 0;
     let v = input.take(|c| { cond(c) && { let ok = i < n; i += 1; ok } });
     if v.len() != n {
-        return parse_error!(Expected::Token(None, None));
+        return parse_error!(expected_token::<I::Token, _, _>(input, None));
     }
 
     Ok(v)
@@ -373,8 +422,8 @@ pub fn take_n_if<I, F>(input: &mut Pear<I>, n: usize, mut cond: F) -> Result<I::
 /// all of the tokens in between. The tokens in between must match `cond`.
 /// Succeeds even if there are no tokens between `start` and `end`.
 #[parser(raw)]
-pub fn delimited<I, T, F>(
-    input: &mut Pear<I>,
+pub fn delimited<I, T, F, U>(
+    input: &mut Pear<I, U>,
     start: T,
     mut cond: F,
     end: T,
@@ -393,8 +442,8 @@ pub fn delimited<I, T, F>(
 /// all of the tokens in between. The tokens in between must match `cond`. There
 /// must be at least one token between `start` and `end`.
 #[parser(raw)]
-pub fn delimited_some<I, T, F>(
-    input: &mut Pear<I>,
+pub fn delimited_some<I, T, F, U>(
+    input: &mut Pear<I, U>,
     start: T,
     mut cond: F,
     end: T,
@@ -411,13 +460,79 @@ pub fn delimited_some<I, T, F>(
 
 /// Succeeds only if the input has reached EOF.
 #[parser(raw)]
-pub fn eof<I: Input>(input: &mut Pear<I>) -> Result<(), I> {
-    Ok(if input.has(1) {
+pub fn eof<I: Input, U>(input: &mut Pear<I, U>) -> Result<(), I> {
+    Ok(if input.is_partial() && !input.has(1) {
+        parse_error!(Expected::Incomplete { needed: None })?
+    } else if let Some((encoding, offset)) = input.malformed() {
+        parse_error!(Expected::Malformed { encoding, offset })?
+    } else if input.has(1) {
         let next = input.token();
         parse_error!(Expected::Eof(next))?
     })
 }
 
+/// A number of bits, shown as `n bits` in errors from the bit parsers.
+struct BitCount(usize);
+
+impl Show for BitCount {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} bits", self.0)
+    }
+}
+
+impl Length for BitCount {
+    fn len(&self) -> usize {
+        self.0
+    }
+}
+
+/// Takes `n` bits and returns the unsigned integer they encode, assembled in
+/// the input's [`BitOrder`](crate::input::BitOrder).
+///
+/// # Panics
+///
+/// Panics if `n` is greater than 64.
+#[parser(raw)]
+pub fn take_bits<'a, U>(input: &mut Pear<Bits<'a>, U>, n: usize) -> Result<u64, Bits<'a>> {
+    assert!(n <= 64, "cannot take {} bits into a u64", n);
+    match input.eat_slice(n, |_| true) {
+        Some(bits) => Ok(bits.value()),
+        None => parse_error!(expected_slice(input, BitCount(n), false)),
+    }
+}
+
+/// Skips to the start of the next byte unless the input is already at the
+/// start of a byte. Returns the number of bits skipped.
+#[parser(raw)]
+pub fn align_to_byte<'a, U>(input: &mut Pear<Bits<'a>, U>) -> Result<usize, Bits<'a>> {
+    let padding = (8 - usize::from(input.position().bit)) % 8;
+    Ok(take_n(padding)?.len())
+}
+
+/// Reads an unsigned Exp-Golomb code, `ue(v)` in H.264: `k` zero bits, a one
+/// bit, and a `k` bit suffix read as by [`take_bits()`]. Returns
+/// `2^k - 1 + suffix`. Fails if `k` is greater than 63.
+#[parser(raw)]
+pub fn exp_golomb<'a, U>(input: &mut Pear<Bits<'a>, U>) -> Result<u64, Bits<'a>> {
+    let zeros = skip_while(|&bit| !bit)?;
+    if zeros > 63 {
+        return parse_error!(Expected::from("exp-Golomb code with at most 63 leading zeros"));
+    }
+
+    eat(true)?;
+    let suffix = take_bits(zeros)?;
+    Ok((1 << zeros) - 1 + suffix)
+}
+
+/// Reads a signed Exp-Golomb code, `se(v)` in H.264. The unsigned codes `0`,
+/// `1`, `2`, `3`, `4`, ... map to `0`, `1`, `-1`, `2`, `-2`, ...
+#[parser(raw)]
+pub fn signed_exp_golomb<'a, U>(input: &mut Pear<Bits<'a>, U>) -> Result<i64, Bits<'a>> {
+    let code = exp_golomb()?;
+    let magnitude = code.div_ceil(2) as i64;
+    Ok(if code % 2 == 1 { magnitude } else { -magnitude })
+}
+
 // // Like delimited, but keeps the start and end tokens.
 // #[parser(raw)]
 // pub fn enclosed<I: Input, F>(
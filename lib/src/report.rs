@@ -0,0 +1,178 @@
+//! Rendering of parse errors with `codespan-reporting`, `ariadne`, or
+//! `miette`.
+//!
+//! Each integration is enabled by the feature of the same name and applies
+//! to errors whose context is a [`Span`] or an [`OwnedSpan`]:
+//!
+//!   * `codespan-reporting`: [`ParseError::to_codespan()`] converts an error
+//!     into a `codespan_reporting` `Diagnostic`.
+//!   * `ariadne`: [`ParseError::to_ariadne()`] converts an error into an
+//!     `ariadne` `ReportBuilder`.
+//!   * `miette`: `ParseError` implements `miette::Diagnostic`. Attach the
+//!     source with `miette::Report::with_source_code()` to render it.
+//!
+//! In each, the error's context is the primary label. The error's labels,
+//! and the contexts of the named parsers on its stack, innermost first, are
+//! secondary labels; the built-in (`raw`) parsers are omitted. The error's
+//! code, notes, and help are carried over where the library supports them.
+//!
+//! ```rust
+//! # #[cfg(feature = "codespan-reporting")] {
+//! use pear::input::{Pear, Text};
+//! use pear::macros::parser;
+//! use pear::parsers::*;
+//!
+//! use codespan_reporting::files::SimpleFile;
+//! use codespan_reporting::term::{emit_into_string, Config};
+//!
+//! #[parser]
+//! fn greeting<'a>(input: &mut Pear<Text<'a>>) -> pear::input::Result<&'a str, Text<'a>> {
+//!     eat_slice("hello")?;
+//!     eat(' ')?;
+//!     eat_slice("world")?
+//! }
+//!
+//! let source = "hello, world";
+//! let error = greeting(&mut Pear::new(source)).unwrap_err().unwrap();
+//! let file = SimpleFile::new("greeting.txt", source);
+//! let report = emit_into_string(&Config::default(), &file, &error.to_codespan(())).unwrap();
+//! assert!(report.starts_with("error: expected token ' ' but found ','"));
+//! # }
+//! ```
+
+use std::fmt::Display;
+use std::ops::Range;
+
+use crate::error::ParseError;
+use crate::input::{Span, OwnedSpan};
+
+/// A range of the input to label.
+struct Labeled {
+    range: Range<usize>,
+    message: Option<String>,
+    primary: bool,
+}
+
+/// The labels of `error`: its context, its labels, and the contexts of the
+/// named parsers on its stack.
+fn labels<C, E>(error: &ParseError<C, E>, range: fn(&C) -> Range<usize>) -> Vec<Labeled> {
+    let primary = Labeled { range: range(&error.info.context), message: None, primary: true };
+    let labels = error.labels().iter().map(|label| Labeled {
+        range: range(&label.context),
+        message: Some(label.message.to_string()),
+        primary: false,
+    });
+
+    let stack = error.stack.iter().filter(|info| !info.parser.raw).map(|info| Labeled {
+        range: range(&info.context),
+        message: Some(match info.count {
+            1 => format!("while parsing `{}`", info.parser.name),
+            n => format!("while parsing `{}` ×{}", info.parser.name, n),
+        }),
+        primary: false,
+    });
+
+    std::iter::once(primary).chain(labels).chain(stack).collect()
+}
+
+macro_rules! impl_reports {
+    ($($C:ty),*) => ($(
+        impl<E: Display> ParseError<$C, E> {
+            /// Converts this error into a `codespan_reporting` diagnostic
+            /// whose labels refer to the file `file_id`.
+            #[cfg(feature = "codespan-reporting")]
+            pub fn to_codespan<F: Clone>(&self, file_id: F) -> codespan_reporting::diagnostic::Diagnostic<F> {
+                use codespan_reporting::diagnostic::{Diagnostic, Label};
+
+                let labels = labels(self, <$C>::range).into_iter().map(|labeled| {
+                    let label = match labeled.primary {
+                        true => Label::primary(file_id.clone(), labeled.range),
+                        false => Label::secondary(file_id.clone(), labeled.range),
+                    };
+
+                    label.with_message(labeled.message.unwrap_or_default())
+                });
+
+                let notes = self.notes().iter().map(|note| format!("note: {}", note));
+                let help = self.help().map(|help| format!("help: {}", help));
+                let diagnostic = Diagnostic::error()
+                    .with_message(&self.error)
+                    .with_labels_iter(labels)
+                    .with_notes_iter(notes.chain(help));
+
+                match self.code() {
+                    Some(code) => diagnostic.with_code(code),
+                    None => diagnostic,
+                }
+            }
+
+            /// Converts this error into an `ariadne` report builder whose
+            /// labels refer to the source `id`.
+            ///
+            /// The builder is configured with byte indices, as spans are in
+            /// bytes. If the configuration is replaced with
+            /// `with_config()`, the new configuration must also use
+            /// `IndexType::Byte`.
+            #[cfg(feature = "ariadne")]
+            pub fn to_ariadne<Id>(&self, id: Id) -> ariadne::ReportBuilder<'static, (Id, Range<usize>)>
+                where Id: std::fmt::Debug + std::hash::Hash + Eq + ToOwned + Clone
+            {
+                use ariadne::{Report, ReportKind, Label, Config, IndexType};
+
+                let span = (id.clone(), <$C>::range(&self.info.context));
+                let config = Config::default().with_index_type(IndexType::Byte);
+                let mut report = Report::build(ReportKind::Error, span)
+                    .with_config(config)
+                    .with_message(&self.error);
+
+                if let Some(code) = self.code() {
+                    report = report.with_code(code);
+                }
+
+                for labeled in labels(self, <$C>::range) {
+                    let mut label = Label::new((id.clone(), labeled.range));
+                    if let Some(message) = labeled.message {
+                        label = label.with_message(message);
+                    }
+
+                    report = report.with_label(label);
+                }
+
+                report.with_notes(self.notes());
+                if let Some(help) = self.help() {
+                    report = report.with_help(help);
+                }
+
+                report
+            }
+        }
+
+        #[cfg(feature = "miette")]
+        impl<E: std::error::Error> miette::Diagnostic for ParseError<$C, E> {
+            fn code<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
+                self.code().map(|code| Box::new(code) as Box<dyn Display>)
+            }
+
+            /// The error's help, followed by its notes.
+            fn help<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
+                let notes = self.notes().iter().map(|note| format!("note: {}", note));
+                let lines: Vec<_> = self.help().map(String::from).into_iter().chain(notes).collect();
+                match lines.is_empty() {
+                    true => None,
+                    false => Some(Box::new(lines.join("\n"))),
+                }
+            }
+
+            fn labels(&self) -> Option<Box<dyn Iterator<Item = miette::LabeledSpan> + '_>> {
+                let labels = labels(self, <$C>::range).into_iter().map(|labeled| match labeled.primary {
+                    true => miette::LabeledSpan::new_primary_with_span(labeled.message, labeled.range),
+                    false => miette::LabeledSpan::new_with_span(labeled.message, labeled.range),
+                });
+
+                Some(Box::new(labels))
+            }
+        }
+    )*)
+}
+
+impl_reports!(Span<'_>, OwnedSpan);
@@ -1,13 +1,26 @@
-use crate::error::ParseError;
+use crate::error::{ParseError, Failure};
 
 /// An alias to a Result where:
 ///
 /// * `Ok` is `T`.
-/// * `Err` is a `ParseError` with context `C` and error `E`
+/// * `Err` is a [`Failure`] of a `ParseError` with context `C` and error `E`
 ///
 /// For a `Result` that is parameterized only by the input type, see
 /// [`input::Result`](crate::input::Result).
-pub type Result<T, C, E> = std::result::Result<T, ParseError<C, E>>;
+///
+/// # Speculative Failures
+///
+/// A parser that fails in a `switch!` arm, [`parse_try!`], or [`ok()`] fails
+/// with [`Failure::Elided`] when its error would be discarded as the next
+/// alternative is tried: no `ParseError` is built for it at all. A failure
+/// that is fatal, because the parser had committed or a limit was exceeded,
+/// is a [`Failure::Error`], as it isn't discarded. [`parse!`] reports the
+/// `ParseError` of a failure.
+///
+/// [`parse_try!`]: crate::macros::parse_try
+/// [`parse!`]: crate::macros::parse
+/// [`ok()`]: crate::combinators::ok()
+pub type Result<T, C, E> = std::result::Result<T, Failure<ParseError<C, E>>>;
 
 #[doc(hidden)]
 pub trait AsResult<T, C, E> {
@@ -28,30 +41,106 @@ impl<T, C, E> AsResult<T, C, E> for Result<T, C, E> {
     }
 }
 
-// // This one will result in inference issues when `Ok(T)` is returned.
-// impl<T, I: Input, E: ::std::fmt::Display> AsResult<T, I> for ::std::result::Result<T, E> {
-//     fn as_result(self) -> Result<T, I> {
-//         let name = unsafe { ::std::intrinsics::type_name::<E>() };
-//         self.map_err(|e| ParseError::new(name, e.to_string()))
-//     }
-// }
-
-// // This one won't but makes some things uglier to write.
-// impl<T, I: Input, E2, E1: Into<E2>> AsResult<T, I, E2> for Result<T, I, E1> {
-//     fn as_result(self) -> Result<T, I, E2> {
-//         match self {
-//             Ok(v) => Ok(v),
-//             Err(e) => Err(ParseError {
-//                 error: e.error.into(),
-//                 contexts: e.contexts
-//             })
-//         }
-//     }
-// }
-
-// // This one won't but makes some things uglier to write.
-// impl<T, I: Input, E> AsResult<T, I, B> for Result<T, I, A> {
-//     fn as_result(self) -> Result<T, I, B> {
-//         self
-//     }
-// }
+/// Lifting of the operand of `?` in a `#[parser]` into a parser `Result`.
+///
+/// The operand is either a parser `Result`, which is left as is, a `Result`
+/// with a bare `ParseError`, whose error is wrapped in a [`Failure::Error`],
+/// or a `Result<T, F>` where `F: std::error::Error`, whose error is wrapped
+/// in an [`Expected::Foreign`] with the context of the current parser. The
+/// first two are told apart from the last by method resolution:
+/// `(&result).lift_kind()` finds `ParseErrorKind` on `&Result<T, Failure<..>>`
+/// and `&Result<T, ParseError<..>>` before it autorefs to find
+/// `ForeignErrorKind`, so the impls don't overlap. The error closure passed to
+/// `ParseKind::lift` takes an `Infallible` so that its argument's type is
+/// known even when it's never called.
+#[doc(hidden)]
+pub mod lift {
+    use std::sync::Arc;
+    use std::convert::Infallible;
+
+    use crate::error::{ParseError, Expected, Failure};
+    use crate::input::{Pear, Input, ParserInfo};
+
+    pub struct ParseKind;
+
+    pub struct ForeignKind;
+
+    pub trait ParseErrorKind {
+        #[inline(always)]
+        fn lift_kind(&self) -> ParseKind {
+            ParseKind
+        }
+    }
+
+    impl<T, C, E> ParseErrorKind for Result<T, Failure<ParseError<C, E>>> {  }
+
+    impl<T, C, E> ParseErrorKind for Result<T, ParseError<C, E>> {  }
+
+    /// The error of a parser `Result` operand.
+    pub trait IntoFailure<C, E> {
+        fn into_failure(self) -> Failure<ParseError<C, E>>;
+    }
+
+    impl<C, E> IntoFailure<C, E> for Failure<ParseError<C, E>> {
+        #[inline(always)]
+        fn into_failure(self) -> Failure<ParseError<C, E>> {
+            self
+        }
+    }
+
+    impl<C, E> IntoFailure<C, E> for ParseError<C, E> {
+        #[inline(always)]
+        fn into_failure(self) -> Failure<ParseError<C, E>> {
+            Failure::Error(self)
+        }
+    }
+
+    pub trait ForeignErrorKind {
+        #[inline(always)]
+        fn lift_kind(&self) -> ForeignKind {
+            ForeignKind
+        }
+    }
+
+    impl<T, F> ForeignErrorKind for &Result<T, F>
+        where F: std::error::Error + Send + Sync + 'static {  }
+
+    impl ParseKind {
+        #[inline(always)]
+        pub fn lift<T, X, C, E, G, R>(self, result: Result<T, X>, _: G)
+            -> Result<T, Failure<ParseError<C, E>>>
+            where X: IntoFailure<C, E>, G: FnOnce(Infallible) -> R
+        {
+            result.map_err(X::into_failure)
+        }
+    }
+
+    impl ForeignKind {
+        #[inline(always)]
+        pub fn lift<T, F, C, E, G>(self, result: Result<T, F>, error: G)
+            -> Result<T, Failure<ParseError<C, E>>>
+            where G: FnOnce(F) -> Failure<ParseError<C, E>>
+        {
+            result.map_err(error)
+        }
+    }
+
+    /// Wraps `error` in a `ParseError` with the context of the parser `info`
+    /// that set `mark`, unless the failure won't be reported.
+    #[cold]
+    pub fn foreign<I, U, F>(
+        input: &mut Pear<I, U>,
+        info: &ParserInfo,
+        mark: I::Marker,
+        error: F,
+    ) -> Failure<crate::input::ParseError<I>>
+        where I: Input, F: std::error::Error + Send + Sync + 'static
+    {
+        if !input.emit_error {
+            return Failure::Elided;
+        }
+
+        let context = input.context(mark);
+        Failure::Error(ParseError::new(*info, Expected::Foreign(Arc::new(error)), context))
+    }
+}
@@ -0,0 +1,125 @@
+//! `serde` support for errors and the contexts of the built-in inputs.
+//!
+//! Most types derive `Serialize` and `Deserialize`. [`Expected`] is
+//! serialized through [`Repr`], which tags each variant with a `kind`.
+//! Strings that must live for `'static`, such as the names of parsers, are
+//! interned when deserialized.
+
+use std::borrow::Cow;
+use std::collections::BTreeSet;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+
+use inlinable_string::InlinableString;
+use serde::{Serialize, Serializer, Deserialize, Deserializer};
+
+use crate::error::Expected;
+use crate::input::{Limit, ParserInfo};
+
+static INTERNED: Mutex<BTreeSet<&'static str>> = Mutex::new(BTreeSet::new());
+
+/// Returns a `'static` copy of `string`. Each distinct string is leaked once
+/// and reused thereafter.
+fn intern(string: String) -> &'static str {
+    let mut interned = INTERNED.lock().unwrap_or_else(|e| e.into_inner());
+    match interned.get(string.as_str()) {
+        Some(interned) => interned,
+        None => {
+            let leaked: &'static str = Box::leak(string.into_boxed_str());
+            interned.insert(leaked);
+            leaked
+        }
+    }
+}
+
+/// The default `count` of an `ErrorInfo`.
+pub(crate) fn one() -> usize {
+    1
+}
+
+pub(crate) fn is_one(count: &usize) -> bool {
+    *count == 1
+}
+
+impl<'de> Deserialize<'de> for ParserInfo {
+    fn deserialize<D: Deserializer<'de>>(de: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        struct Repr {
+            name: String,
+            raw: bool,
+        }
+
+        let repr = Repr::deserialize(de)?;
+        Ok(ParserInfo { name: intern(repr.name), raw: repr.raw })
+    }
+}
+
+/// The serialized form of an [`Expected`].
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+enum Repr<T, S> {
+    Token { expected: Option<String>, found: Option<T> },
+    Slice { expected: Option<String>, found: Option<S> },
+    Eof { found: Option<T> },
+    Incomplete { needed: Option<usize> },
+    Malformed { encoding: Cow<'static, str>, offset: usize },
+    TooDeep { limit: usize },
+    LimitExceeded { limit: Limit },
+    Foreign { message: String },
+    Other { message: String },
+    Elided,
+}
+
+/// A foreign error deserialized from its message.
+#[derive(Debug)]
+struct Message(String);
+
+impl fmt::Display for Message {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for Message {  }
+
+impl<T: Serialize, S: Serialize> Serialize for Expected<T, S> {
+    fn serialize<Ser: Serializer>(&self, serializer: Ser) -> Result<Ser::Ok, Ser::Error> {
+        let string = |s: &Option<InlinableString>| s.as_ref().map(|s| s.to_string());
+        let repr: Repr<&T, &S> = match self {
+            Expected::Token(e, v) => Repr::Token { expected: string(e), found: v.as_ref() },
+            Expected::Slice(e, v) => Repr::Slice { expected: string(e), found: v.as_ref() },
+            Expected::Eof(v) => Repr::Eof { found: v.as_ref() },
+            Expected::Incomplete { needed } => Repr::Incomplete { needed: *needed },
+            Expected::Malformed { encoding, offset } => {
+                Repr::Malformed { encoding: Cow::Borrowed(encoding), offset: *offset }
+            }
+            Expected::TooDeep { limit } => Repr::TooDeep { limit: *limit },
+            Expected::LimitExceeded(limit) => Repr::LimitExceeded { limit: *limit },
+            Expected::Foreign(e) => Repr::Foreign { message: e.to_string() },
+            Expected::Other(message) => Repr::Other { message: message.to_string() },
+            Expected::Elided => Repr::Elided,
+        };
+
+        repr.serialize(serializer)
+    }
+}
+
+impl<'de, T: Deserialize<'de>, S: Deserialize<'de>> Deserialize<'de> for Expected<T, S> {
+    fn deserialize<D: Deserializer<'de>>(de: D) -> Result<Self, D::Error> {
+        let string = |s: Option<String>| s.map(InlinableString::from);
+        Ok(match Repr::<T, S>::deserialize(de)? {
+            Repr::Token { expected, found } => Expected::Token(string(expected), found),
+            Repr::Slice { expected, found } => Expected::Slice(string(expected), found),
+            Repr::Eof { found } => Expected::Eof(found),
+            Repr::Incomplete { needed } => Expected::Incomplete { needed },
+            Repr::Malformed { encoding, offset } => {
+                Expected::Malformed { encoding: intern(encoding.into_owned()), offset }
+            }
+            Repr::TooDeep { limit } => Expected::TooDeep { limit },
+            Repr::LimitExceeded { limit } => Expected::LimitExceeded(limit),
+            Repr::Foreign { message } => Expected::Foreign(Arc::new(Message(message))),
+            Repr::Other { message } => Expected::from(message),
+            Repr::Elided => Expected::Elided,
+        })
+    }
+}
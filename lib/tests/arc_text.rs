@@ -0,0 +1,55 @@
+use std::collections::HashMap;
+
+use pear::input::{Pear, ArcText, ArcStr, OwnedSpan, ParseError};
+use pear::{macros::*, parsers::*, combinators::*};
+
+type Input = Pear<ArcText>;
+type Result<T> = pear::input::Result<T, ArcText>;
+
+#[parser]
+fn assignment(input: &mut Input) -> Result<(ArcStr, ArcStr)> {
+    let key = take_some_while(|c| c.is_alphanumeric())?;
+    eat_slice(" = ")?;
+    let value = take_some_while(|&c| c != '\n')?;
+    eat('\n')?;
+    (key, value)
+}
+
+#[parser]
+fn assignments(input: &mut Input) -> Result<HashMap<ArcStr, ArcStr>> {
+    let pairs: HashMap<_, _> = try_collect(assignment)?;
+    eof()?;
+    pairs
+}
+
+#[allow(clippy::result_large_err)]
+fn load(source: String) -> std::result::Result<HashMap<ArcStr, ArcStr>, ParseError<ArcText>> {
+    parse!(assignments: ArcText::from(source))
+}
+
+#[test]
+fn test_arc_text_outlives_source() {
+    let config = load("name = pear\nkind = fruit\n".to_string()).unwrap();
+    assert_eq!(config["name"], "pear");
+    assert_eq!(config["kind"], "fruit");
+
+    let kind = config["kind"].clone();
+    assert_eq!(kind.range(), 19..24);
+    assert_eq!(&kind.source()[..4], "name");
+}
+
+#[test]
+fn test_arc_text_error_is_static() {
+    let error = std::thread::spawn(|| {
+        let mut input = Pear::new(ArcText::from("name = pear\nkind ! fruit\n".to_string()));
+        assignment(&mut input).unwrap();
+        assignment(&mut input).unwrap_err().unwrap()
+    }).join().unwrap();
+
+    assert_eq!(error.info.context, OwnedSpan {
+        start: (2, 5, 16),
+        end: (2, 5, 16),
+        cursor: Some(' '),
+        snippet: Some("".into()),
+    });
+}
@@ -0,0 +1,80 @@
+#![cfg(feature = "tokio")]
+
+use tokio::io::AsyncWriteExt;
+
+use pear::input::{AsyncInput, AsyncStream, StreamError, Pear};
+use pear::{macros::*, parsers::*};
+
+type Result<'a, T> = pear::input::Result<T, AsyncInput<'a>>;
+
+#[derive(Debug, PartialEq)]
+struct Header {
+    name: String,
+    value: String,
+}
+
+fn string(bytes: &[u8]) -> String {
+    String::from_utf8_lossy(bytes).into_owned()
+}
+
+#[parser]
+fn header<'a>(input: &mut Pear<AsyncInput<'a>>) -> Result<'a, Header> {
+    let name = take_some_while(|&b| b != b':' && b != b'\r')?;
+    eat_slice(&b": "[..])?;
+    let value = take_while(|&b| b != b'\r')?;
+    eat_slice(&b"\r\n"[..])?;
+    Header { name: string(&name), value: string(&value) }
+}
+
+#[tokio::test]
+async fn test_async_refills() {
+    let (mut client, server) = tokio::io::duplex(4);
+    let writer = tokio::spawn(async move {
+        client.write_all(b"Host: example.com\r\nAccept: */*\r\n").await.unwrap();
+    });
+
+    let mut stream = AsyncStream::with_chunk_size(server, 3);
+    let host = stream.parse(header).await.unwrap();
+    assert_eq!(host, Some(Header { name: "Host".into(), value: "example.com".into() }));
+
+    let accept = stream.parse(header).await.unwrap();
+    assert_eq!(accept, Some(Header { name: "Accept".into(), value: "*/*".into() }));
+
+    writer.await.unwrap();
+    assert_eq!(stream.parse(header).await.unwrap(), None);
+}
+
+#[tokio::test]
+async fn test_async_errors() {
+    let (mut client, server) = tokio::io::duplex(64);
+    client.write_all(b"Host example.com\r\n").await.unwrap();
+
+    let mut stream = AsyncStream::new(server);
+    match stream.parse(header).await {
+        Err(StreamError::Parse(e)) => assert_eq!(e.info.context.start, 16),
+        result => panic!("unexpected result: {:?}", result),
+    }
+
+    let (mut client, server) = tokio::io::duplex(64);
+    client.write_all(b"Host: exam").await.unwrap();
+    drop(client);
+
+    let mut stream = AsyncStream::new(server);
+    assert!(matches!(stream.parse(header).await, Err(StreamError::Parse(_))));
+    assert_eq!(stream.buffered(), b"Host: exam");
+}
+
+#[tokio::test]
+#[cfg(feature = "futures-io")]
+async fn test_futures_reader() {
+    use pear::input::FuturesRead;
+
+    let reader = FuturesRead(&b"Host: example.com\r\nAccept: */*\r\n"[..]);
+    let mut stream = AsyncStream::with_chunk_size(reader, 3);
+    let host = stream.parse(header).await.unwrap();
+    assert_eq!(host, Some(Header { name: "Host".into(), value: "example.com".into() }));
+
+    let accept = stream.parse(header).await.unwrap();
+    assert_eq!(accept, Some(Header { name: "Accept".into(), value: "*/*".into() }));
+    assert_eq!(stream.parse(header).await.unwrap(), None);
+}
@@ -0,0 +1,113 @@
+use pear::input::{Pear, Bits, BitSpan, BitPosition, Length};
+use pear::error::Expected;
+use pear::{macros::*, parsers::*, combinators::*};
+
+type Input<'a> = Pear<Bits<'a>>;
+type Result<'a, T> = pear::input::Result<T, Bits<'a>>;
+
+#[derive(Debug, PartialEq)]
+struct Sps {
+    profile_idc: u64,
+    level_idc: u64,
+    seq_parameter_set_id: u64,
+    log2_max_frame_num_minus4: u64,
+    pic_order_cnt_type: u64,
+}
+
+#[parser]
+fn sps<'a>(input: &mut Input<'a>) -> Result<'a, Sps> {
+    let profile_idc = take_bits(8)?;
+    let _constraint_flags = take_bits(8)?;
+    let level_idc = take_bits(8)?;
+    let seq_parameter_set_id = exp_golomb()?;
+    let log2_max_frame_num_minus4 = exp_golomb()?;
+    let pic_order_cnt_type = exp_golomb()?;
+    align_to_byte()?;
+    eof()?;
+
+    Sps {
+        profile_idc,
+        level_idc,
+        seq_parameter_set_id,
+        log2_max_frame_num_minus4,
+        pic_order_cnt_type,
+    }
+}
+
+#[test]
+fn test_msb_first() {
+    let bytes = [66, 0, 30, 0b1101_1000];
+    assert_eq!(parse!(sps: Bits::msb_first(&bytes)).unwrap(), Sps {
+        profile_idc: 66,
+        level_idc: 30,
+        seq_parameter_set_id: 0,
+        log2_max_frame_num_minus4: 0,
+        pic_order_cnt_type: 2,
+    });
+}
+
+#[test]
+fn test_signed_exp_golomb() {
+    // 1 010 011 00100 00101, then padding.
+    let bytes = [0b1010_0110, 0b0100_0010, 0b1000_0000];
+    let mut input = Pear::from(Bits::msb_first(&bytes));
+    let values: Vec<i64> = (0..5).map(|_| signed_exp_golomb(&mut input).unwrap()).collect();
+    assert_eq!(values, [0, 1, -1, 2, -2]);
+    assert_eq!(align_to_byte(&mut input).unwrap(), 7);
+    assert!(eof(&mut input).is_ok());
+}
+
+#[test]
+fn test_lsb_first() {
+    // A DEFLATE block header: BFINAL = 1, BTYPE = 2, then HLIT = 29.
+    let bytes = [0b1110_1101, 0b0000_0011];
+    let mut input = Pear::from(Bits::lsb_first(&bytes));
+    assert_eq!(take_bits(&mut input, 1).unwrap(), 1);
+    assert_eq!(take_bits(&mut input, 2).unwrap(), 2);
+    assert_eq!(take_bits(&mut input, 5).unwrap(), 29);
+    assert_eq!(take_bits(&mut input, 4).unwrap(), 3);
+    assert_eq!(align_to_byte(&mut input).unwrap(), 4);
+    assert_eq!(align_to_byte(&mut input).unwrap(), 0);
+}
+
+#[test]
+fn test_bit_slices() {
+    let mut input = Pear::from(Bits::msb_first(&[0b1001_0000]));
+    assert!(peek_slice(&mut input, &[true, false]).is_ok());
+    assert!(eat_slice(&mut input, &[true, false, true]).is_err());
+
+    let slice = eat_slice(&mut input, &[true, false, false]).unwrap();
+    assert_eq!(slice.value(), 0b100);
+    assert_eq!(slice.start(), BitPosition { byte: 0, bit: 0 });
+    assert_eq!(slice.end(), BitPosition { byte: 0, bit: 3 });
+    assert!(eat(&mut input, true).unwrap());
+    assert_eq!(take_while(&mut input, |&bit| !bit).unwrap().len(), 4);
+}
+
+#[parser]
+fn record<'a>(input: &mut Input<'a>) -> Result<'a, u64> {
+    eat_slice(&[true, false])?;
+    take_bits(4)?
+}
+
+#[test]
+fn test_rewind() {
+    let bytes = [0b1010_1110, 0b0000_1000];
+    let mut input = Pear::from(Bits::msb_first(&bytes));
+    let records: Vec<_> = try_collect(&mut input, record).unwrap();
+    assert_eq!(records, [0b1011, 0b0000]);
+    assert_eq!(input.position(), BitPosition { byte: 1, bit: 4 });
+}
+
+#[test]
+fn test_error_context() {
+    let mut input = Pear::from(Bits::msb_first(&[0xFF]));
+    take_bits(&mut input, 3).unwrap();
+
+    let error = take_bits(&mut input, 16).unwrap_err().unwrap();
+    assert!(matches!(error.error, Expected::Slice(Some(ref s), None) if &**s == "16 bits"));
+    assert_eq!(error.info.context, BitSpan {
+        start: BitPosition { byte: 0, bit: 3 },
+        end: BitPosition { byte: 0, bit: 3 },
+    });
+}
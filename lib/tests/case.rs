@@ -0,0 +1,92 @@
+use pear::input::{Pear, Text, Cursor, CaseFold, Folding};
+use pear::error::Expected;
+use pear::{macros::*, parsers::*};
+
+type Result<'a, T> = pear::input::Result<T, Text<'a>>;
+
+#[parser]
+fn boolean<'a>(input: &mut Pear<Text<'a>>) -> Result<'a, bool> {
+    switch! {
+        eat_slice_ignore_case("true") => true,
+        eat_slice_ignore_case("false") => false,
+        _ => parse_error!("expected boolean")?
+    }
+}
+
+#[test]
+fn test_slice_ignore_case() {
+    assert!(parse!(boolean: Text::from("TRUE")).unwrap());
+    assert!(!parse!(boolean: Text::from("False")).unwrap());
+    assert!(parse!(boolean: Text::from("yes")).is_err());
+
+    let mut input: Pear<Text<'_>> = Pear::new("Content-Length: 5");
+    peek_slice_ignore_case(&mut input, "content-length").unwrap();
+    let name = eat_slice_ignore_case(&mut input, "content-length").unwrap();
+    assert_eq!(name, "Content-Length");
+
+    let mut input: Pear<Cursor<&[u8]>> = Pear::new(&b"HOST: x"[..]);
+    let name = eat_slice_ignore_case(&mut input, &b"host"[..]).unwrap();
+    assert_eq!(name, &b"HOST"[..]);
+}
+
+#[test]
+fn test_slice_ignore_case_error() {
+    let mut input: Pear<Text<'_>> = Pear::new("selext");
+    let error = eat_slice_ignore_case(&mut input, "select").unwrap_err().unwrap();
+    assert!(matches!(error.error, Expected::Slice(Some(ref s), _) if s == "\"select\" (case-insensitive)"));
+    assert_eq!(
+        error.error.to_string(),
+        "expected slice \"select\" (case-insensitive) but found \"selext\""
+    );
+}
+
+#[test]
+fn test_case_fold_ascii() {
+    let mut input = Pear::from(CaseFold::ascii(Text::from("SeLeCt Name FROM")));
+    assert_eq!(*eat_slice(&mut input, "select").unwrap(), "SeLeCt");
+    assert_eq!(eat(&mut input, ' ').unwrap(), ' ');
+    assert_eq!(eat(&mut input, 'n').unwrap(), 'n');
+    assert_eq!(take_while(&mut input, |&c| c != ' ').unwrap(), "ame");
+    assert!(eat_slice(&mut input, " from ").is_err());
+    assert_eq!(*eat_slice(&mut input, " from").unwrap(), " FROM");
+    eof(&mut input).unwrap();
+
+    let mut input = Pear::from(CaseFold::ascii(Text::from("ÉTÉ")));
+    assert!(eat(&mut input, 'é').is_err());
+}
+
+#[test]
+fn test_case_fold_unicode() {
+    let mut input = Pear::from(CaseFold::new(Text::from("ΣΟΦΟΣ Été"), Folding::Unicode));
+    assert_eq!(*eat_slice(&mut input, "σοφος").unwrap(), "ΣΟΦΟΣ");
+    eat(&mut input, ' ').unwrap();
+    assert_eq!(eat(&mut input, 'é').unwrap(), 'é');
+    assert_eq!(*eat_slice(&mut input, "TÉ").unwrap(), "té");
+}
+
+#[test]
+fn test_case_fold_bytes() {
+    let mut input = Pear::from(CaseFold::ascii(Cursor::from(&b"GET /"[..])));
+    assert_eq!(eat_slice(&mut input, &b"get"[..]).unwrap().values, &b"GET"[..]);
+    assert_eq!(eat(&mut input, b' ').unwrap(), b' ');
+}
+
+#[test]
+fn test_case_fold_error() {
+    let mut input = Pear::from(CaseFold::ascii(Text::from("selext")));
+    let error = eat_slice(&mut input, "select").unwrap_err().unwrap();
+    assert_eq!(
+        error.error.to_string(),
+        "expected slice \"select\" (case-insensitive) but found \"selext\""
+    );
+}
+
+#[test]
+fn test_caseless_eq_symmetric() {
+    let mut ascii = Pear::from(CaseFold::ascii(Text::from("ÉTÉ")));
+    let mut unicode = Pear::from(CaseFold::unicode(Text::from("été")));
+    let ascii = eat_slice(&mut ascii, "ÉTÉ").unwrap();
+    let unicode = eat_slice(&mut unicode, "ÉTÉ").unwrap();
+    assert!(ascii != unicode);
+    assert!(unicode != ascii);
+}
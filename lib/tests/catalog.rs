@@ -0,0 +1,134 @@
+use std::fmt;
+
+use pear::input::{Pear, Text, Limit};
+use pear::error::Expected;
+use pear::catalog::{Catalog, English, Messages, Localize, CatalogError, ENGLISH};
+use pear::{macros::*, parsers::*};
+
+type Input<'a> = Pear<Text<'a>>;
+type Result<'a, T> = pear::input::Result<T, Text<'a>>;
+
+const SPANISH: &str = r#"
+# Mensajes de error en español.
+expected-token-found = se esperaba { $expected } pero se encontró { $found }
+expected-eof-found = se esperaba el final pero se encontró { $found }
+stack = en { $parser } ({ $context })
+note = nota: { $note }
+limit-input-length =
+    la entrada tiene más
+    de { $max } elementos { "{" }sic{ "}" }
+"#;
+
+#[parser]
+fn pair<'a>(input: &mut Input<'a>) -> Result<'a, (char, char)> {
+    let a = eat_any()?;
+    eat(',')?;
+    let b = eat_any()?;
+    eof()?;
+    (a, b)
+}
+
+#[test]
+fn test_english_display() {
+    let error = parse!(pair: Text::from("a;b")).unwrap_err();
+    let english = error.localized(&English).to_string();
+    assert_eq!(english, error.to_string());
+    assert!(english.starts_with("expected token ',' but found ';'\n + eat 1:2"));
+
+    for id in ENGLISH.lines().filter_map(|line| line.split_once(" = ")).map(|(id, _)| id) {
+        assert!(Messages::parse(ENGLISH).unwrap().get(id).is_some(), "{}", id);
+    }
+}
+
+#[test]
+fn test_localized() {
+    let spanish = Messages::parse(SPANISH).unwrap();
+
+    let error = parse!(pair: Text::from("a;b")).unwrap_err();
+    let message = error.localized(&spanish).to_string();
+    let lines: Vec<_> = message.lines().collect();
+    assert_eq!(lines[0], "se esperaba ',' pero se encontró ';'");
+    assert!(lines[1].starts_with(" + en eat (1:2"));
+    assert!(lines.last().unwrap().starts_with(" + en pair (1:1"));
+
+    let error = parse!(pair: Text::from("a,bc")).unwrap_err();
+    assert_eq!(error.error.localized(&spanish).to_string(), "se esperaba el final pero se encontró 'c'");
+
+    // Missing messages fall back to English.
+    let error = parse!(pair: Text::from("a")).unwrap_err();
+    assert_eq!(error.error.localized(&spanish).to_string(), error.error.to_string());
+
+    let limit = Limit::InputLength(10);
+    assert_eq!(limit.localized(&spanish).to_string(), "la entrada tiene más\nde 10 elementos {sic}");
+    let expected: Expected<char, &str> = Expected::LimitExceeded(limit);
+    assert_eq!(expected.localized(&spanish).to_string(), limit.localized(&spanish).to_string());
+    assert_eq!(expected.to_string(), "input is longer than the maximum length of 10");
+}
+
+#[test]
+fn test_localized_details() {
+    let spanish = Messages::parse(SPANISH).unwrap();
+    let error = parse!(pair: Text::from("a;b")).unwrap_err()
+        .with_code("E1")
+        .with_note("las comas separan")
+        .with_help("use a comma");
+
+    let message = error.localized(&spanish).to_string();
+    assert!(message.starts_with("E1: se esperaba"));
+    assert!(message.ends_with("\n = nota: las comas separan\n = help: use a comma"));
+}
+
+#[test]
+fn test_catalog_errors() {
+    let error = Messages::parse("\n  b\na = c").unwrap_err();
+    assert_eq!(error, CatalogError { line: 2, reason: "continuation line without a message" });
+
+    assert_eq!(Messages::parse("\n\nno equals sign").unwrap_err().line, 3);
+    assert_eq!(Messages::parse("1abc = x").unwrap_err().reason, "invalid message id");
+    assert_eq!(Messages::parse("a = { $b").unwrap_err().reason, "unclosed `{`");
+    assert!(Messages::parse("a = { b }").is_err());
+    assert!(Messages::parse("a = { \"b }").is_err());
+    assert!(Messages::parse("a = x\n  { $y").is_err());
+}
+
+#[derive(Debug)]
+enum Error {
+    Expected(Expected<char, &'static str>),
+    Reserved(&'static str),
+}
+
+/// A catalog that upper-cases every English message.
+struct Shouting;
+
+impl Catalog for Shouting {
+    fn message(&self, f: &mut fmt::Formatter<'_>, id: &str, args: &[(&str, &dyn fmt::Display)]) -> fmt::Result {
+        struct Message<'a>(&'a str, &'a [(&'a str, &'a dyn fmt::Display)]);
+
+        impl fmt::Display for Message<'_> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                English.message(f, self.0, self.1)
+            }
+        }
+
+        write!(f, "{}", Message(id, args).to_string().to_uppercase())
+    }
+}
+
+impl Localize for Error {
+    fn localize(&self, f: &mut fmt::Formatter<'_>, catalog: &dyn Catalog) -> fmt::Result {
+        match self {
+            Error::Expected(e) => e.localize(f, catalog),
+            Error::Reserved(word) => catalog.message(f, "reserved-word", &[("word", word)]),
+        }
+    }
+}
+
+#[test]
+fn test_custom_errors() {
+    let catalog = Messages::parse("reserved-word = `{ $word }` is reserved").unwrap();
+    assert_eq!(Error::Reserved("fn").localized(&catalog).to_string(), "`fn` is reserved");
+    assert_eq!(Error::Reserved("fn").localized(&English).to_string(), "reserved-word");
+
+    let error = Error::Expected(Expected::Token(Some("'a'".into()), Some('b')));
+    assert_eq!(error.localized(&Shouting).to_string(), "EXPECTED TOKEN 'A' BUT FOUND 'B'");
+}
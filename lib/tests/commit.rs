@@ -0,0 +1,129 @@
+use pear::input::{Pear, Text};
+use pear::error::Expected;
+use pear::{macros::*, parsers::*, combinators::*};
+
+type Input<'a> = Pear<Text<'a>>;
+type Result<'a, T> = pear::input::Result<T, Text<'a>>;
+
+#[derive(Debug, PartialEq)]
+enum Value<'a> {
+    Object(Vec<(&'a str, Value<'a>)>),
+    Ident(&'a str),
+}
+
+#[parser]
+fn ident<'a>(input: &mut Input<'a>) -> Result<'a, &'a str> {
+    take_some_while(|c| c.is_ascii_alphabetic())?
+}
+
+#[parser]
+fn object<'a>(input: &mut Input<'a>) -> Result<'a, Vec<(&'a str, Value<'a>)>> {
+    eat('{')?;
+    parse_commit!();
+    let entries: Vec<_> = trailing_series(|i| {
+        let key = ident(i)?;
+        eat(i, ':')?;
+        Ok((key, value(i)?))
+    }, ',')?;
+
+    eat('}')?;
+    entries
+}
+
+#[parser]
+fn value<'a>(input: &mut Input<'a>) -> Result<'a, Value<'a>> {
+    switch! {
+        o@object() => Value::Object(o),
+        i@ident() => Value::Ident(i),
+        _ => parse_error!(Expected::from("a value"))?
+    }
+}
+
+#[test]
+fn test_commit_switch() {
+    let parsed = value(&mut Input::new("{a:b,c:{d:e}}")).unwrap();
+    assert_eq!(parsed, Value::Object(vec![
+        ("a", Value::Ident("b")),
+        ("c", Value::Object(vec![("d", Value::Ident("e"))])),
+    ]));
+
+    // Without the commit, the error would be the fallback's "a value".
+    let mut input = Input::new("{a:b;}");
+    let error = value(&mut input).unwrap_err().unwrap();
+    assert!(matches!(error.error, Expected::Token(Some(ref s), Some(';')) if &**s == "'}'"));
+    assert!(input.is_fatal());
+}
+
+#[test]
+fn test_commit_before_cut() {
+    // Failing before `parse_commit!()` is not fatal.
+    let mut input = Input::new("(");
+    let error = value(&mut input).unwrap_err().unwrap();
+    assert!(matches!(error.error, Expected::Other(ref s) if &**s == "a value"));
+    assert!(!input.is_fatal());
+}
+
+#[parser]
+fn maybe_object<'a>(input: &mut Input<'a>) -> Result<'a, bool> {
+    parse_try!(object()).is_some()
+}
+
+#[test]
+fn test_commit_parse_try() {
+    assert!(maybe_object(&mut Input::new("{a:b}")).unwrap());
+    assert!(!maybe_object(&mut Input::new("a")).unwrap());
+    assert!(maybe_object(&mut Input::new("{a:b")).is_err());
+}
+
+#[parser(commit)]
+fn keyword<'a>(input: &mut Input<'a>) -> Result<'a, &'a str> {
+    eat_slice("let ")?
+}
+
+#[test]
+fn test_commit_attribute_try_collect() {
+    let mut input = Input::new("let let ");
+    let keywords: Vec<_> = try_collect(&mut input, keyword).unwrap();
+    assert_eq!(keywords.len(), 2);
+
+    let mut input = Input::new("let let x");
+    let result: Result<'_, Vec<_>> = try_collect(&mut input, keyword);
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_ok_and_attempt() {
+    let mut input = Input::new("{a:");
+    assert!(ok(&mut input, object).is_none());
+    assert!(!input.is_fatal());
+
+    let mut input = Input::new("{a:");
+    assert!(attempt(&mut input, object).is_err());
+
+    let mut input = Input::new("a");
+    assert_eq!(attempt(&mut input, object).unwrap(), None);
+    assert!(!input.is_fatal());
+}
+
+#[parser]
+fn bang_after_object<'a>(input: &mut Input<'a>) -> Result<'a, char> {
+    let _ = ok(input, object);
+    eat('!')?
+}
+
+#[parser]
+fn bang_or_default<'a>(input: &mut Input<'a>) -> Result<'a, char> {
+    switch! {
+        c@bang_after_object() => c,
+        _ => '?'
+    }
+}
+
+#[test]
+fn test_ok_discards_fatal() {
+    // The committed failure discarded by `ok()` doesn't make the failing
+    // `switch!` arm fatal.
+    let mut input = Input::new("{a:");
+    assert_eq!(bang_or_default(&mut input).unwrap(), '?');
+    assert!(!input.is_fatal());
+}
@@ -0,0 +1,126 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use pear::input::{Pear, Text, Expected, Limit, Input as _};
+use pear::error::Expected as Kind;
+use pear::{macros::*, parsers::*, combinators::*};
+
+#[derive(Debug)]
+enum IniError<'a> {
+    Expected(Expected<Text<'a>>),
+    DuplicateKey(&'a str),
+    BadNumber(&'a str, std::num::ParseIntError),
+}
+
+impl<'a> From<Expected<Text<'a>>> for IniError<'a> {
+    fn from(expected: Expected<Text<'a>>) -> Self {
+        IniError::Expected(expected)
+    }
+}
+
+impl fmt::Display for IniError<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IniError::Expected(e) => write!(f, "{}", e),
+            IniError::DuplicateKey(key) => write!(f, "duplicate key `{}`", key),
+            IniError::BadNumber(value, e) => write!(f, "bad number `{}`: {}", value, e),
+        }
+    }
+}
+
+type Input<'a> = Pear<Text<'a>>;
+type Result<'a, T> = pear::input::Result<T, Text<'a>, IniError<'a>>;
+
+#[parser]
+fn key<'a>(input: &mut Input<'a>) -> Result<'a, &'a str> {
+    take_some_while(|c| c.is_ascii_alphanumeric())?
+}
+
+#[parser]
+fn number<'a>(input: &mut Input<'a>) -> Result<'a, i64> {
+    let value = take_some_while(|c| c.is_ascii_alphanumeric() || *c == '-')?;
+    match value.parse::<i64>() {
+        Ok(number) => number,
+        Err(e) => parse_error!(IniError::BadNumber(value, e))?,
+    }
+}
+
+#[parser]
+fn entry<'a>(input: &mut Input<'a>) -> Result<'a, (&'a str, i64)> {
+    let key = key()?;
+    eat_slice(" = ")?;
+    let value = number()?;
+    eat('\n')?;
+    (key, value)
+}
+
+#[parser]
+fn ini<'a>(input: &mut Input<'a>) -> Result<'a, HashMap<&'a str, i64>> {
+    let mut map = HashMap::new();
+    while input.token().is_some() {
+        let (key, value) = entry()?;
+        if map.insert(key, value).is_some() {
+            parse_error!(IniError::DuplicateKey(key))?;
+        }
+    }
+
+    map
+}
+
+#[parser]
+fn version<'a>(input: &mut Input<'a>) -> Result<'a, i64> {
+    let version = number()?;
+    if version < 1 {
+        parse_error!("version {} is older than 1", version)?;
+    }
+
+    version
+}
+
+#[test]
+fn test_ok() {
+    let map = parse!(ini: Text::from("a = 1\nb = -2\n")).unwrap();
+    assert_eq!(map["a"], 1);
+    assert_eq!(map["b"], -2);
+}
+
+#[test]
+fn test_domain_errors() {
+    let error = parse!(ini: Text::from("a = 1\na = 2\n")).unwrap_err();
+    assert!(matches!(error.error, IniError::DuplicateKey("a")));
+    assert!(error.to_string().starts_with("duplicate key `a`\n + ini 1:1 to 3:1"));
+
+    let error = parse!(ini: Text::from("a = 1\nb = 2x\n")).unwrap_err();
+    assert!(matches!(error.error, IniError::BadNumber("2x", _)));
+    assert_eq!(error.info.parser.name, "number");
+    assert_eq!(error.info.context.start, (2, 5, 10));
+
+    let names: Vec<_> = error.stack.iter().map(|info| info.parser.name).collect();
+    assert_eq!(names, ["number", "entry", "ini"]);
+}
+
+#[test]
+fn test_expected_errors() {
+    let error = parse!(ini: Text::from("a: 1\n")).unwrap_err();
+    assert!(matches!(error.error, IniError::Expected(Kind::Slice(..))));
+
+    let error = parse!(version: Text::from("0")).unwrap_err();
+    assert!(matches!(error.error, IniError::Expected(Kind::Other(..))));
+    assert_eq!(error.error.to_string(), "version 0 is older than 1");
+
+    let mut input = Input::new("a = 1\n").with_fuel(2);
+    let error = ini(&mut input).unwrap_err().unwrap();
+    assert!(matches!(error.error, IniError::Expected(Kind::LimitExceeded(Limit::Fuel))));
+
+    // Limit and depth failures are built as `Expected` and converted.
+    let mut input = Input::new("a = 1\n").with_max_depth(1);
+    let error = ini(&mut input).unwrap_err().unwrap();
+    assert!(matches!(error.error, IniError::Expected(Kind::TooDeep { limit: 1 })));
+}
+
+#[test]
+fn test_combinators() {
+    let mut input = Input::new("1 2 x");
+    let result: Result<'_, Vec<i64>> = series(&mut input, number, ' ');
+    assert!(matches!(result.unwrap_err().unwrap().error, IniError::BadNumber("x", _)));
+}
@@ -0,0 +1,84 @@
+use pear::input::{Pear, Text, Show};
+use pear::error::Expected;
+use pear::{macros::*, parsers::*};
+
+type Input<'a> = Pear<Text<'a>>;
+type Result<'a, T> = pear::input::Result<T, Text<'a>>;
+
+#[parser]
+fn nested<'a>(input: &mut Input<'a>) -> Result<'a, usize> {
+    switch! {
+        eat('[') => {
+            let depth = nested()? + 1;
+            eat(']')?;
+            depth
+        },
+        _ => 0
+    }
+}
+
+#[parser]
+fn list<'a>(input: &mut Input<'a>) -> Result<'a, usize> {
+    eat('[')?;
+    let depth = value()? + 1;
+    eat(']')?;
+    depth
+}
+
+#[parser]
+fn value<'a>(input: &mut Input<'a>) -> Result<'a, usize> {
+    switch! {
+        depth@list() => depth,
+        _ => 0
+    }
+}
+
+fn brackets(depth: usize) -> String {
+    "[".repeat(depth) + &"]".repeat(depth)
+}
+
+#[test]
+fn test_within_limit() {
+    let source = brackets(100);
+    let mut input = Input::new(source.as_str()).with_max_depth(101);
+    assert_eq!(nested(&mut input).unwrap(), 100);
+}
+
+#[test]
+fn test_too_deep() {
+    let source = brackets(100);
+    let mut input = Input::new(source.as_str()).with_max_depth(100);
+    let error = nested(&mut input).unwrap_err().unwrap();
+    assert!(matches!(error.error, Expected::TooDeep { limit: 100 }));
+    assert_eq!(error.info.context.start, (1, 101, 100));
+
+    let message = format!("{} at {}", error.error, &error.info.context as &dyn Show);
+    assert!(message.starts_with("nesting too deep: more than 100 levels at 1:101"));
+}
+
+#[test]
+fn test_too_deep_not_backtracked() {
+    let source = brackets(10);
+    let mut input = Input::new(source.as_str()).with_max_depth(22);
+    assert_eq!(value(&mut input).unwrap(), 10);
+
+    let mut input = Input::new(source.as_str()).with_max_depth(8);
+    let error = value(&mut input).unwrap_err().unwrap();
+    assert!(matches!(error.error, Expected::TooDeep { limit: 8 }));
+    assert!(input.is_fatal());
+}
+
+#[test]
+fn test_deep_input() {
+    let source = brackets(20_000);
+    let error = parse!(nested: &mut Input::new(source.as_str()).with_max_depth(64)).unwrap_err();
+    assert!(matches!(error.error, Expected::TooDeep { limit: 64 }));
+    assert_eq!(error.info.context.start, (1, 65, 64));
+}
+
+#[test]
+#[cfg(feature = "stacker")]
+fn test_stack_growth() {
+    let source = brackets(20_000);
+    assert_eq!(parse!(nested: Text::from(source.as_str())).unwrap(), 20_000);
+}
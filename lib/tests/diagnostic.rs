@@ -0,0 +1,98 @@
+use pear::input::{Pear, Text, Span, Input as _};
+use pear::error::Expected;
+use pear::{macros::*, parsers::*};
+
+type Input<'a> = Pear<Text<'a>>;
+type Result<'a, T> = pear::input::Result<T, Text<'a>>;
+
+#[parser]
+fn section<'a>(input: &mut Input<'a>) -> Result<'a, (&'a str, Span<'a>)> {
+    eat('[')?;
+    let name = take_some_while(|c| c.is_alphabetic())?;
+    eat(']')?;
+    (name, parse_context!())
+}
+
+#[parser]
+fn ini<'a>(input: &mut Input<'a>) -> Result<'a, Vec<&'a str>> {
+    let mut sections: Vec<(&str, Span<'_>)> = vec![];
+    while input.token().is_some() {
+        let (name, span) = section()?;
+        if let Some((_, first)) = sections.iter().find(|(n, _)| *n == name) {
+            parse_error!("duplicate section name `{}`", name;
+                code = "E0042",
+                label(*first) = "first defined here",
+                note = "section names are case-sensitive",
+                help = format!("rename one of the `{}` sections", name))?;
+        }
+
+        sections.push((name, span));
+        skip_while(|c| *c == '\n')?;
+    }
+
+    sections.into_iter().map(|(name, _)| name).collect::<Vec<_>>()
+}
+
+#[parser]
+fn digit<'a>(input: &mut Input<'a>) -> Result<'a, char> {
+    switch! {
+        d@eat_if(|c| c.is_ascii_digit()) => d,
+        c@peek_any() => parse_error!(Expected::Token(None, Some(c)); help = "expected a digit")?,
+        _ => parse_error!("{}", "no digit",)?
+    }
+}
+
+#[test]
+fn test_metadata() {
+    assert_eq!(parse!(ini: Text::from("[a]\n[b]\n")).unwrap(), ["a", "b"]);
+
+    let error = parse!(ini: Text::from("[a]\n[b]\n[a]")).unwrap_err();
+    assert_eq!(error.code(), Some("E0042"));
+    assert_eq!(error.help(), Some("rename one of the `a` sections"));
+    assert_eq!(error.notes(), ["section names are case-sensitive"]);
+    assert_eq!(error.labels().len(), 1);
+    assert_eq!(error.labels()[0].message, "first defined here");
+    assert_eq!(error.labels()[0].context.start, (1, 1, 0));
+    assert_eq!(error.labels()[0].context.end, (1, 4, 3));
+}
+
+#[test]
+fn test_display() {
+    let error = parse!(ini: Text::from("[a]\n[a]")).unwrap_err();
+    let string = error.to_string();
+    let lines: Vec<_> = string.lines().collect();
+    assert_eq!(lines[0], "E0042: duplicate section name `a`");
+    assert!(lines[1].starts_with(" + ini 1:1"));
+    assert!(lines[2].starts_with(" - first defined here 1:1 to 1:4"));
+    assert_eq!(lines[3], " = note: section names are case-sensitive");
+    assert_eq!(lines[4], " = help: rename one of the `a` sections");
+}
+
+#[test]
+fn test_no_metadata() {
+    let error = parse!(digit: Text::from("")).unwrap_err();
+    assert!(error.diagnostic.is_none());
+    assert_eq!(error.code(), None);
+    assert!(error.notes().is_empty() && error.labels().is_empty());
+    assert_eq!(error.error.to_string(), "no digit");
+
+    let error = parse!(digit: Text::from("x")).unwrap_err();
+    assert_eq!(error.help(), Some("expected a digit"));
+    assert!(error.to_string().ends_with("\n = help: expected a digit"));
+}
+
+#[test]
+fn test_metadata_conversions() {
+    let error = parse!(ini: Text::from("[a]\n[a]")).unwrap_err();
+    let error = error.into::<Expected<char, &str>>().into_owned();
+    assert_eq!(error.code(), Some("E0042"));
+    assert_eq!(error.labels()[0].context.snippet.as_deref(), Some("[a]"));
+
+    let error = parse!(digit: Text::from("x")).unwrap_err()
+        .with_code("E0001")
+        .with_note("a note");
+
+    assert_eq!(error.code(), Some("E0001"));
+    assert_eq!(error.help(), Some("expected a digit"));
+    assert_eq!(error.notes(), ["a note"]);
+}
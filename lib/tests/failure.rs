@@ -0,0 +1,135 @@
+use pear::input::{Pear, Text, ArcText, ParseError, Rewind};
+use pear::error::{Expected, Failure};
+use pear::{macros::*, parsers::*, combinators::*};
+
+type Input<'a> = Pear<Text<'a>>;
+type Result<'a, T> = pear::input::Result<T, Text<'a>>;
+
+#[parser(rewind)]
+fn digit<'a>(input: &mut Input<'a>) -> Result<'a, u32> {
+    skip_while(|c| *c == '\n')?;
+    let c = eat_any()?;
+    match c.to_digit(10) {
+        Some(d) => d,
+        None => parse_error!("expected a digit, found {:?}", c; help = "use 0-9")?,
+    }
+}
+
+#[parser]
+fn number<'a>(input: &mut Input<'a>) -> Result<'a, u8> {
+    take_some_while(|c| c.is_ascii_digit())?.parse::<u8>()?
+}
+
+#[parser]
+fn committed<'a>(input: &mut Input<'a>) -> Result<'a, char> {
+    eat('(')?;
+    parse_commit!();
+    eat(')')?
+}
+
+#[parser]
+fn digits<'a>(input: &mut Input<'a>) -> Result<'a, Vec<u32>> {
+    let mut digits = vec![];
+    while let Some(d) = parse_try!(digit()) {
+        digits.push(d);
+    }
+
+    eof()?;
+    digits
+}
+
+#[parser]
+fn elided<'a>(input: &mut Input<'a>) -> Result<'a, char> {
+    let failure: Result<'a, char> = Err(Failure::Elided);
+    failure?
+}
+
+/// Runs `p` as if in a `switch!` arm, returning its failure.
+fn speculatively<'a, T>(text: &'a str, p: fn(&mut Input<'a>) -> Result<'a, T>) -> Failure<ParseError<Text<'a>>> {
+    speculatively_with(text, p, true)
+}
+
+fn speculatively_with<'a, T>(
+    text: &'a str,
+    p: fn(&mut Input<'a>) -> Result<'a, T>,
+    elide: bool,
+) -> Failure<ParseError<Text<'a>>> {
+    let mut input = Input::new(text);
+    input.options.stacked_context = true;
+    input.options.elide_failures = elide;
+    input.speculate();
+    match p(&mut input) {
+        Ok(_) => panic!("expected a failure"),
+        Err(e) => e,
+    }
+}
+
+#[test]
+fn test_failures_are_elided() {
+    assert!(speculatively("\n\nx", digit).is_elided());
+    assert!(speculatively("x", eof).is_elided());
+    assert!(speculatively("300", number).is_elided());
+    assert!(speculatively("(]", committed).as_error().is_some());
+}
+
+#[test]
+fn test_failures_built_in_full() {
+    let error = speculatively_with("\n\nx", digit, false).unwrap();
+    assert_eq!(error.error.to_string(), "expected a digit, found 'x'");
+    assert_eq!(error.help(), Some("use 0-9"));
+    assert_eq!(error.stack.len(), 1);
+}
+
+#[test]
+fn test_context_positions() {
+    let mut input = Pear::<ArcText>::new("ab\ncd\nef");
+    skip_while(&mut input, |c| *c != 'f').unwrap();
+    let error = eat(&mut input, 'x').unwrap_err().unwrap();
+    assert_eq!(error.info.context.end, (3, 2, 7));
+
+    // Positions are found from the last one, forward or backward.
+    input.rewind_to(3);
+    let error = eat(&mut input, 'x').unwrap_err().unwrap();
+    assert_eq!(error.info.context.end, (2, 1, 3));
+    input.rewind_to(1);
+    let error = eat(&mut input, 'x').unwrap_err().unwrap();
+    assert_eq!(error.info.context.end, (1, 2, 1));
+}
+
+#[test]
+fn test_reported_errors_have_details() {
+    let error = parse!(digit: Text::from("\n\nx")).unwrap_err();
+    assert_eq!(error.error.to_string(), "expected a digit, found 'x'");
+    assert_eq!((error.info.context.start, error.info.context.end), ((1, 1, 0), (3, 2, 3)));
+    assert_eq!(error.help(), Some("use 0-9"));
+
+    let error = parse!(number: Text::from("300")).unwrap_err();
+    assert!(matches!(error.error, Expected::Foreign(_)));
+
+    // The failure of the last `parse_try!` is elided; the reported error is
+    // `eof()`'s, which isn't speculative.
+    let error = parse!(digits: Text::from("12x")).unwrap_err();
+    assert_eq!(error.error.to_string(), "expected EOF but found 'x'");
+    assert_eq!(error.info.context.end, (1, 3, 2));
+
+    // An elided failure that escapes to the top level is still reported.
+    let error = parse!(elided: Text::from("a")).unwrap_err();
+    assert!(matches!(error.error, Expected::Elided));
+    assert_eq!(error.info.parser.name, "elided");
+    assert_eq!(error.to_string(), "[ERROR ELIDED]");
+}
+
+#[test]
+fn test_fatal_failures_have_details() {
+    let mut input = Input::new("(]");
+    input.emit_error = false;
+    let error = attempt(&mut input, committed).unwrap_err().unwrap();
+    assert_eq!(error.error.to_string(), "expected token ')' but found ']'");
+    assert_eq!(error.info.context.start, (1, 2, 1));
+
+    let mut input = Input::new("\n\n123").with_fuel(3);
+    input.emit_error = false;
+    let error = digits(&mut input).unwrap_err().unwrap();
+    assert!(matches!(error.error, Expected::LimitExceeded(_)));
+    assert_ne!(error.info.context.start.0, 0);
+}
@@ -0,0 +1,86 @@
+use std::error::Error;
+use std::num::ParseIntError;
+
+use pear::input::{Pear, Text, Expected};
+use pear::error::Expected as Kind;
+use pear::{macros::*, parsers::*};
+
+type Input<'a> = Pear<Text<'a>>;
+type Result<'a, T> = pear::input::Result<T, Text<'a>>;
+
+#[parser]
+fn number<'a>(input: &mut Input<'a>) -> Result<'a, u8> {
+    take_some_while(|c| c.is_ascii_alphanumeric())?.parse::<u8>()?
+}
+
+#[parser]
+fn pair<'a>(input: &mut Input<'a>) -> Result<'a, (u8, u8)> {
+    let a = number()?;
+    eat(',')?;
+    let b = take_some_while(|c| c.is_ascii_digit())?;
+    (a, b.parse::<u8>()?)
+}
+
+#[parser]
+fn byte_or_word<'a>(input: &mut Input<'a>) -> Result<'a, Option<u8>> {
+    switch! {
+        n@number() => Some(n),
+        _ => None
+    }
+}
+
+#[test]
+fn test_foreign_ok() {
+    assert_eq!(parse!(number: Text::from("255")).unwrap(), 255);
+    assert_eq!(parse!(pair: Text::from("1,2")).unwrap(), (1, 2));
+}
+
+#[test]
+fn test_foreign_error() {
+    let error = parse!(number: Text::from("256")).unwrap_err();
+    assert!(matches!(error.error, Kind::Foreign(..)));
+    assert_eq!(error.info.parser.name, "number");
+    assert_eq!(error.info.context.start, (1, 1, 0));
+    assert_eq!(error.info.context.end, (1, 4, 3));
+    assert!(error.to_string().starts_with("number too large to fit in target type\n + number"));
+
+    let error = parse!(pair: Text::from("1,300")).unwrap_err();
+    assert_eq!(error.info.parser.name, "pair");
+    assert_eq!(error.info.context.end, (1, 6, 5));
+}
+
+#[test]
+fn test_foreign_source() {
+    let error = parse!(number: Text::from("x")).unwrap_err();
+    let expected: &dyn Error = &error.error;
+    let source = expected.source().expect("foreign error source");
+    assert!(source.downcast_ref::<ParseIntError>().is_some());
+}
+
+#[test]
+fn test_foreign_backtracks() {
+    let mut input = Input::new("abc");
+    assert_eq!(byte_or_word(&mut input).unwrap(), None);
+}
+
+#[derive(Debug)]
+enum Error2<'a> {
+    Expected(Expected<Text<'a>>),
+}
+
+impl<'a> From<Expected<Text<'a>>> for Error2<'a> {
+    fn from(expected: Expected<Text<'a>>) -> Self {
+        Error2::Expected(expected)
+    }
+}
+
+#[parser]
+fn custom<'a>(input: &mut Pear<Text<'a>>) -> pear::input::Result<u8, Text<'a>, Error2<'a>> {
+    "x".parse::<u8>()?
+}
+
+#[test]
+fn test_foreign_custom_error() {
+    let error = custom(&mut Input::new("")).unwrap_err().unwrap();
+    assert!(matches!(error.error, Error2::Expected(Kind::Foreign(..))));
+}
@@ -0,0 +1,99 @@
+use std::borrow::Cow;
+
+use pear::input::{Pear, Indexed, IndexedInput, Extent, Length};
+use pear::{macros::*, parsers::*, combinators::*};
+
+type Input<'a> = Pear<IndexedInput<'a, str>>;
+type Result<'a, T> = pear::input::Result<T, IndexedInput<'a, str>>;
+
+#[derive(Debug, PartialEq)]
+struct Header<'a> {
+    name: Indexed<'a, str>,
+    value: Indexed<'a, str>,
+}
+
+#[parser]
+fn header<'a>(input: &mut Input<'a>) -> Result<'a, Header<'a>> {
+    let name = take_some_while(|&c| c.is_ascii_alphanumeric() || c == '-')?;
+    eat_slice(": ")?;
+    let value = take_some_while(|&c| c != '\n')?;
+    eat('\n')?;
+    Header { name, value }
+}
+
+#[parser]
+fn headers<'a>(input: &mut Input<'a>) -> Result<'a, Vec<Header<'a>>> {
+    let headers: Vec<_> = try_collect(header)?;
+    eof()?;
+    headers
+}
+
+#[test]
+fn test_indexed_str() {
+    let source = "Host: example.com\nContent-Length: 10\n";
+    let headers = parse!(headers: IndexedInput::from(source)).unwrap();
+    assert_eq!(headers, vec![
+        Header { name: Indexed::Indexed(0, 4), value: Indexed::Indexed(6, 17) },
+        Header { name: Indexed::Indexed(18, 32), value: Indexed::Indexed(34, 36) },
+    ]);
+
+    let resolve = |i: &Indexed<'_, str>| i.to_source(Some(source)).to_string();
+    assert_eq!(resolve(&headers[0].name), "Host");
+    assert_eq!(resolve(&headers[0].value), "example.com");
+    assert_eq!(resolve(&headers[1].name), "Content-Length");
+    assert_eq!(resolve(&headers[1].value), "10");
+}
+
+#[test]
+fn test_indexed_bytes() {
+    let source: &[u8] = b"GET /index";
+    let mut input: Pear<IndexedInput<'_, [u8]>> = Pear::new(source);
+    let method = take_while(&mut input, |&b| b != b' ').unwrap();
+    let space = eat_slice(&mut input, &b" "[..]).unwrap();
+    let path = take_while(&mut input, |_| true).unwrap();
+
+    assert_eq!(method, Indexed::Indexed(0, 3));
+    assert_eq!(path.to_source(Some(source)), b"/index");
+    assert_eq!(method.clone() + space + path, Indexed::Indexed(0, 10));
+}
+
+#[test]
+fn test_concrete() {
+    let source = "abcdef";
+    let indexed: Indexed<'_, str> = Indexed::Indexed(1, 4);
+    assert!(indexed.is_indexed());
+    assert_eq!(indexed.len(), 3);
+    assert_eq!(indexed.into_concrete(Some(source)), "bcd");
+
+    let concrete = Indexed::from_cow(Cow::Owned::<str>("xyz".into()));
+    assert!(!concrete.is_indexed());
+    assert_eq!(concrete.to_source(None), "xyz");
+    assert_eq!(concrete.clone().into_concrete(None), "xyz");
+    assert_eq!(concrete.into_owned(), Indexed::from("xyz"));
+}
+
+#[test]
+fn test_checked_from() {
+    let source = "Host: example.com";
+    let host = &source[6..];
+    assert_eq!(Indexed::checked_from(host, source), Some(Indexed::Indexed(6, 17)));
+    assert_eq!(Indexed::checked_from(source, host), None);
+    assert_eq!(Indexed::checked_from(&*String::from("example"), source), None);
+    assert_eq!(unsafe { Indexed::unchecked_from(host, source) }, Indexed::Indexed(6, 17));
+
+    let bytes = &b"GET /"[..];
+    assert_eq!(Indexed::checked_from(&bytes[4..], bytes), Some(Indexed::Indexed(4, 5)));
+}
+
+#[test]
+#[should_panic]
+fn test_add_non_adjacent() {
+    let _ = Indexed::<str>::Indexed(0, 2) + Indexed::Indexed(3, 4);
+}
+
+#[test]
+fn test_error_context() {
+    let source = "Host: example.com\nBad header\n";
+    let error = parse!(headers: IndexedInput::from(source)).unwrap_err();
+    assert_eq!(error.info.context, Extent { start: 18, end: 18, values: "" });
+}
@@ -0,0 +1,128 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use pear::input::{Pear, Text, Limit, Options};
+use pear::error::Expected;
+use pear::{macros::*, parsers::*, combinators::*};
+
+type Input<'a> = Pear<Text<'a>>;
+type Result<'a, T> = pear::input::Result<T, Text<'a>>;
+
+#[parser]
+fn word<'a>(input: &mut Input<'a>) -> Result<'a, &'a str> {
+    let word = take_some_while(|c| c.is_alphabetic())?;
+    skip_while(|c| *c == ' ')?;
+    word
+}
+
+#[parser]
+fn words<'a>(input: &mut Input<'a>) -> Result<'a, Vec<&'a str>> {
+    let words: Vec<_> = try_collect(word)?;
+    words
+}
+
+#[parser]
+fn word_or_nothing<'a>(input: &mut Input<'a>) -> Result<'a, Option<&'a str>> {
+    switch! {
+        w@word() => Some(w),
+        _ => None
+    }
+}
+
+fn assert_exceeded<T>(result: Result<'_, T>, limit: Limit) {
+    match result {
+        Err(e) => assert!(matches!(e.unwrap().error, Expected::LimitExceeded(l) if l == limit)),
+        Ok(_) => panic!("expected {:?} to be exceeded", limit),
+    }
+}
+
+#[test]
+fn test_fuel() {
+    let mut input = Input::new("a bb ccc").with_fuel(1_000);
+    assert_eq!(words(&mut input).unwrap(), ["a", "bb", "ccc"]);
+    assert!(input.options.fuel.unwrap() < 1_000);
+    assert_eq!(input.exceeded(), None);
+
+    let mut input = Input::new("a bb ccc").with_fuel(10);
+    assert_exceeded(words(&mut input), Limit::Fuel);
+    assert_eq!(input.exceeded(), Some(Limit::Fuel));
+
+    // Every parser fails once the budget is exhausted.
+    assert_exceeded(eof(&mut input), Limit::Fuel);
+}
+
+#[test]
+fn test_tokens_spend_fuel() {
+    let long = "x".repeat(100);
+    assert!(word(&mut Input::new(long.as_str()).with_fuel(200)).is_ok());
+    assert_exceeded(word(&mut Input::new(long.as_str()).with_fuel(50)), Limit::Fuel);
+}
+
+#[test]
+fn test_no_backtracking() {
+    // `word` exhausts the budget; `switch!` must not fall back to `None`.
+    let mut input = Input::new("abcdef").with_fuel(4);
+    assert_exceeded(word_or_nothing(&mut input), Limit::Fuel);
+
+    let mut input = Input::new("abcdef").with_fuel(100);
+    assert_eq!(word_or_nothing(&mut input).unwrap(), Some("abcdef"));
+}
+
+#[test]
+fn test_max_input_length() {
+    let mut input = Input::new("a bb ccc").with_max_input_length(8);
+    assert_eq!(words(&mut input).unwrap().len(), 3);
+
+    let mut input = Input::new("a bb ccc").with_max_input_length(7);
+    assert_exceeded(words(&mut input), Limit::InputLength(7));
+
+    // The length of text is measured in UTF-8 bytes.
+    let mut input = Input::new("héllo").with_max_input_length(5);
+    assert_exceeded(words(&mut input), Limit::InputLength(5));
+}
+
+#[test]
+fn test_options_reset_limits() {
+    let mut input = Input::new("a bb ccc").with_fuel(2);
+    assert_exceeded(words(&mut input), Limit::Fuel);
+    assert!(input.is_fatal());
+
+    input.set_options(Options { fuel: Some(1_000), ..Options::default() });
+    assert_eq!(input.exceeded(), None);
+    assert!(!input.is_fatal());
+    assert_eq!(words(&mut input).unwrap(), ["a", "bb", "ccc"]);
+
+    let mut input = Input::new("a bb ccc").with_max_input_length(1);
+    assert_exceeded(words(&mut input), Limit::InputLength(1));
+    let mut input = input.with_max_input_length(8);
+    assert_eq!(words(&mut input).unwrap().len(), 3);
+}
+
+#[test]
+fn test_cancellation() {
+    let cancel = Arc::new(AtomicBool::new(true));
+    let mut input = Input::new("a bb ccc").with_cancellation(cancel.clone());
+    assert_exceeded(words(&mut input), Limit::Cancelled);
+
+    // Cancel while `try_collect` is running: it must not stop silently.
+    cancel.store(false, Ordering::Relaxed);
+    let mut input = Input::new("a bb ccc").with_cancellation(cancel.clone());
+    let mut count = 0;
+    #[allow(clippy::result_large_err)]
+    let result: Result<'_, Vec<_>> = try_collect(&mut input, |i| {
+        count += 1;
+        if count == 2 {
+            cancel.store(true, Ordering::Relaxed);
+        }
+
+        word(i)
+    });
+
+    assert_exceeded(result, Limit::Cancelled);
+}
+
+#[test]
+fn test_display() {
+    let error = words(&mut Input::new("a").with_fuel(0)).unwrap_err().unwrap();
+    assert_eq!(error.error.to_string(), "parsing budget exhausted");
+}
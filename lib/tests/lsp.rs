@@ -0,0 +1,126 @@
+#![cfg(feature = "lsp-types")]
+
+use pear::input::{Pear, Text, Span, Input as _};
+use pear::lsp::Document;
+use pear::{macros::*, parsers::*};
+
+use lsp_types::{Position, Range, NumberOrString, DiagnosticSeverity, CodeActionKind};
+
+type Input<'a> = Pear<Text<'a>>;
+type Result<'a, T> = pear::input::Result<T, Text<'a>>;
+
+#[parser]
+fn entry<'a>(input: &mut Input<'a>) -> Result<'a, (&'a str, Span<'a>)> {
+    let name = take_some_while(|c| c.is_alphanumeric() || !c.is_ascii())?;
+    eat('=')?;
+    let _value = switch! {
+        eat_slice("true") => true,
+        eat_slice("false") => false,
+        _ => parse_error!("expected a boolean")?
+    };
+
+    eat('\n')?;
+    (name, parse_context!())
+}
+
+#[parser]
+fn entries<'a>(input: &mut Input<'a>) -> Result<'a, Vec<&'a str>> {
+    let mut seen: Vec<(&str, Span<'_>)> = vec![];
+    while input.token().is_some() {
+        let (name, span) = entry()?;
+        if let Some((_, first)) = seen.iter().find(|(n, _)| *n == name) {
+            parse_error!("duplicate key `{}`", name;
+                code = "E0001",
+                label(*first) = "first defined here",
+                note = "keys are case-sensitive",
+                help = "remove one of the entries")?;
+        }
+
+        seen.push((name, span));
+    }
+
+    seen.into_iter().map(|(name, _)| name).collect::<Vec<_>>()
+}
+
+fn document(text: &str) -> Document<'_> {
+    Document::new("file:///config.ini".parse().unwrap(), text)
+}
+
+#[test]
+fn test_utf16_positions() {
+    let text = "a=true\n𝒳é=fals\n";
+    let mut input = Input::new(text);
+    let error = entries(&mut input).unwrap_err().unwrap();
+    let diagnostic = document(text).diagnostic(&error);
+
+    // `𝒳` is 4 bytes and 2 UTF-16 units; `é` is 2 bytes and 1 unit.
+    assert_eq!((error.info.context.start, error.info.context.end), ((2, 1, 7), (2, 8, 14)));
+    assert_eq!(diagnostic.range, Range::new(Position::new(1, 0), Position::new(1, 4)));
+    assert_eq!(diagnostic.message, "expected a boolean");
+    assert_eq!(diagnostic.severity, Some(DiagnosticSeverity::ERROR));
+    assert_eq!(diagnostic.code, None);
+
+    let related = diagnostic.related_information.unwrap();
+    assert_eq!(related[0].message, "while parsing `entry`");
+    assert_eq!(related[0].location.range, Range::new(Position::new(1, 0), Position::new(1, 4)));
+    assert_eq!(related[1].message, "while parsing `entries`");
+    assert_eq!(related[1].location.range.start, Position::new(0, 0));
+    assert_eq!(related.len(), 2);
+}
+
+#[test]
+fn test_metadata() {
+    let text = "ключ=true\nключ=false\n";
+    let error = entries(&mut Input::new(text)).unwrap_err().unwrap();
+    let diagnostic = document(text).with_source("ini").diagnostic(&error);
+
+    assert_eq!(diagnostic.code, Some(NumberOrString::String("E0001".into())));
+    assert_eq!(diagnostic.source.as_deref(), Some("ini"));
+    assert_eq!(diagnostic.message, "duplicate key `ключ`\n\
+        note: keys are case-sensitive\n\
+        help: remove one of the entries");
+
+    let related = diagnostic.related_information.unwrap();
+    assert_eq!(related[0].message, "first defined here");
+    assert_eq!(related[0].location.uri.as_str(), "file:///config.ini");
+    assert_eq!(related[0].location.range, Range::new(Position::new(0, 0), Position::new(1, 0)));
+    assert_eq!(related[1].message, "while parsing `entries`");
+}
+
+#[test]
+fn test_recovered_errors() {
+    let text = "a=true\nb=nope\nc=yes\n";
+    let errors: Vec<_> = text.split_inclusive('\n')
+        .filter_map(|line| entry(&mut Input::new(line)).err().and_then(|e| e.error()))
+        .collect();
+
+    let document = document(text).with_severity(DiagnosticSeverity::WARNING);
+    let diagnostics = document.diagnostics(&errors);
+    assert_eq!(diagnostics.len(), 2);
+    assert!(diagnostics.iter().all(|d| d.severity == Some(DiagnosticSeverity::WARNING)));
+}
+
+#[test]
+fn test_code_actions() {
+    let text = "ok=ture\n";
+    let mut input = Input::new(text).with_suggestions();
+    let error = entries(&mut input).unwrap_err().unwrap();
+
+    let document = document(text);
+    let actions = document.code_actions(&error);
+    assert_eq!(actions.len(), 1);
+
+    let action = &actions[0];
+    assert_eq!(action.title, "replace with `true`");
+    assert_eq!(action.kind, Some(CodeActionKind::QUICKFIX));
+    assert_eq!(action.is_preferred, Some(true));
+    assert_eq!(action.diagnostics.as_ref().unwrap()[0], document.diagnostic(&error));
+
+    let edits = &action.edit.as_ref().unwrap().changes.as_ref().unwrap()[document.uri()];
+    assert_eq!(edits[0].new_text, "true");
+    assert_eq!(edits[0].range, Range::new(Position::new(0, 3), Position::new(0, 7)));
+
+    let error = entries(&mut Input::new(text)).unwrap_err().unwrap();
+    assert!(document.code_actions(&error).is_empty());
+}
+
@@ -0,0 +1,82 @@
+use std::error::Error;
+use std::num::ParseIntError;
+
+use pear::input::{Pear, Text, Cursor, Extent, OwnedSpan};
+use pear::error::{ParseError, Expected};
+use pear::{macros::*, parsers::*};
+
+type Input<'a> = Pear<Text<'a>>;
+type Result<'a, T> = pear::input::Result<T, Text<'a>>;
+
+type OwnedError = ParseError<OwnedSpan, Expected<char, String>>;
+type OwnedBytesError = ParseError<Extent<Vec<u8>>, Expected<u8, Extent<Vec<u8>>>>;
+
+#[parser]
+fn number<'a>(input: &mut Input<'a>) -> Result<'a, u8> {
+    take_some_while(|c| c.is_ascii_alphanumeric())?.parse::<u8>()?
+}
+
+#[parser]
+fn pair<'a>(input: &mut Input<'a>) -> Result<'a, (u8, u8)> {
+    let a = number()?;
+    eat(',')?;
+    (a, number()?)
+}
+
+#[allow(clippy::result_large_err)]
+fn parse_pair(source: String) -> std::result::Result<(u8, u8), OwnedError> {
+    parse!(pair: Text::from(source.as_str())).map_err(|e| e.into_owned())
+}
+
+#[test]
+fn test_owned_outlives_input() {
+    let error = parse_pair("1;2".into()).unwrap_err();
+    assert!(matches!(error.error, Expected::Token(Some(ref s), Some(';')) if &**s == "','"));
+    assert_eq!(error.info.parser.name, "eat");
+    assert_eq!(error.info.context.start, (1, 2, 1));
+    assert_eq!(error.stack.len(), 2);
+    assert_eq!(error.stack[1].context.snippet.as_deref(), Some("1"));
+}
+
+#[test]
+fn test_owned_display() {
+    let source = String::from("1;2");
+    let error = parse!(pair: Text::from(source.as_str())).unwrap_err();
+    let expected = error.to_string();
+    assert_eq!(error.into_owned().to_string(), expected);
+}
+
+#[test]
+fn test_boxed_error() {
+    let error: Box<dyn Error + Send + Sync> = Box::new(parse_pair("1,300".into()).unwrap_err());
+    assert!(error.to_string().starts_with("number too large to fit in target type"));
+
+    let source = error.source().expect("foreign error source");
+    assert!(source.downcast_ref::<ParseIntError>().is_some());
+
+    let error: Box<dyn Error> = Box::new(parse_pair("1;2".into()).unwrap_err());
+    assert!(error.source().is_none());
+}
+
+#[test]
+fn test_send_owned() {
+    let error = std::thread::spawn(|| parse_pair("x,1".into()).unwrap_err())
+        .join()
+        .unwrap();
+
+    assert_eq!(error.info.parser.name, "number");
+}
+
+#[test]
+fn test_owned_extent() {
+    let error = {
+        let bytes = [1u8, 2, 3];
+        let mut input = Pear::<Cursor<&[u8]>>::new(&bytes[..]);
+        eat(&mut input, 1u8).unwrap();
+        eat(&mut input, 3u8).unwrap_err().unwrap().into_owned()
+    };
+
+    let _: &OwnedBytesError = &error;
+    assert!(matches!(error.error, Expected::Token(_, Some(2))));
+    assert_eq!((error.info.context.start, error.info.context.end), (1, 1));
+}
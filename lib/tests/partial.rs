@@ -0,0 +1,119 @@
+use pear::input::{Pear, Partial, Cursor, Stream, Status, Options, Limit};
+use pear::error::Expected;
+use pear::{macros::*, parsers::*};
+
+type Input<'a> = Partial<Cursor<&'a [u8]>>;
+type Result<'a, T> = pear::input::Result<T, Input<'a>>;
+
+#[parser]
+fn token<'a>(input: &mut Pear<Input<'a>>) -> Result<'a, &'a [u8]> {
+    take_some_while(|&b| b != b' ' && b != b'\r')?.values
+}
+
+#[parser]
+fn request_line<'a>(input: &mut Pear<Input<'a>>) -> Result<'a, (&'a [u8], &'a [u8])> {
+    let method = switch! {
+        m@eat_slice(&b"GET"[..]) | m@eat_slice(&b"POST"[..]) => m.values,
+        _ => parse_error!("unknown method")?
+    };
+
+    eat(b' ')?;
+    let path = token()?;
+    eat(b' ')?;
+    eat_slice(&b"HTTP/1.1\r\n"[..])?;
+    (method, path)
+}
+
+#[test]
+fn test_partial_incomplete_error() {
+    let mut input = Pear::from(Partial::new(Cursor::from(&b"GET /index HTT"[..])));
+    let error = request_line(&mut input).unwrap_err().unwrap();
+    assert!(matches!(error.error, Expected::Incomplete { needed: Some(7) }));
+    assert_eq!(input.needed(), Some(7));
+
+    let mut input = Pear::from(Partial::new(Cursor::from(&b"GET /index HTT"[..])));
+    input.complete();
+    let error = request_line(&mut input).unwrap_err().unwrap();
+    assert!(matches!(error.error, Expected::Slice(..)));
+    assert!(!input.is_starved());
+}
+
+#[test]
+fn test_partial_starves_in_switch() {
+    let mut stream = Stream::new();
+    stream.feed(b"PO");
+    assert!(matches!(stream.parse(request_line), Status::Incomplete(Some(2))));
+
+    stream.feed(b"ST /a HTTP/1.1\r\n");
+    match stream.parse(request_line) {
+        Status::Complete((method, path)) => {
+            assert_eq!(method, b"POST");
+            assert_eq!(path, b"/a");
+        }
+        status => panic!("unexpected status: {:?}", status),
+    }
+}
+
+#[test]
+fn test_stream_resumes_from_committed() {
+    let mut stream = Stream::new();
+    for chunk in [&b"GET /in"[..], b"dex HTTP/1.", b"1\r", b"\nGET /b HTTP/1.1\r\nPO"].iter() {
+        assert!(matches!(stream.parse(request_line), Status::Incomplete(_)));
+        stream.feed(chunk);
+    }
+
+    match stream.parse(request_line) {
+        Status::Complete(line) => assert_eq!(line, (&b"GET"[..], &b"/index"[..])),
+        status => panic!("unexpected status: {:?}", status),
+    }
+
+    match stream.parse(request_line) {
+        Status::Complete(line) => assert_eq!(line, (&b"GET"[..], &b"/b"[..])),
+        status => panic!("unexpected status: {:?}", status),
+    }
+
+    assert!(matches!(stream.parse(request_line), Status::Incomplete(_)));
+    assert_eq!(stream.buffered(), b"PO");
+}
+
+#[test]
+fn test_stream_syntax_error() {
+    let mut stream = Stream::new();
+    stream.feed(b"GET /index HTTX/1.1\r\n");
+    assert!(matches!(stream.parse(request_line), Status::Error(_)));
+
+    let mut stream = Stream::new();
+    stream.feed(b"PUT /index");
+    assert!(matches!(stream.parse(request_line), Status::Error(_)));
+
+    let mut stream = Stream::new();
+    stream.feed(b"GET /index");
+    stream.complete();
+    match stream.parse(request_line) {
+        Status::Error(e) => assert!(matches!(e.error, Expected::Token(..))),
+        status => panic!("unexpected status: {:?}", status),
+    }
+}
+
+#[parser]
+fn limited<'a>(input: &mut Pear<Input<'a>>) -> Result<'a, (&'a [u8], &'a [u8])> {
+    input.set_options(Options { max_input_length: Some(20), ..Options::default() });
+    request_line()?
+}
+
+#[test]
+fn test_stream_max_input_length() {
+    let mut stream = Stream::new();
+    stream.feed(b"GET /a HTTP/1.1\r\n");
+    assert!(matches!(stream.parse(limited), Status::Complete(_)));
+
+    stream.feed(b"GET /index.html HTTP/1.1\r\n");
+    assert!(matches!(stream.parse(limited), Status::Complete(_)));
+
+    stream.feed(b"GET /index.html HTTP/1.1\r\n");
+    stream.complete();
+    match stream.parse(limited) {
+        Status::Error(e) => assert!(matches!(e.error, Expected::LimitExceeded(Limit::InputLength(20)))),
+        status => panic!("unexpected status: {:?}", status),
+    }
+}
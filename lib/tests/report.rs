@@ -0,0 +1,115 @@
+#![cfg(any(feature = "codespan-reporting", feature = "ariadne", feature = "miette"))]
+
+use pear::input::{Pear, Text, Span, Input as _};
+use pear::{macros::*, parsers::*};
+
+type Input<'a> = Pear<Text<'a>>;
+type Result<'a, T> = pear::input::Result<T, Text<'a>>;
+
+#[parser]
+fn section<'a>(input: &mut Input<'a>) -> Result<'a, (&'a str, Span<'a>)> {
+    eat('[')?;
+    let name = take_some_while(|c| c.is_alphabetic())?;
+    eat(']')?;
+    (name, parse_context!())
+}
+
+#[parser]
+fn ini<'a>(input: &mut Input<'a>) -> Result<'a, Vec<&'a str>> {
+    let mut sections: Vec<(&str, Span<'_>)> = vec![];
+    while input.token().is_some() {
+        let (name, span) = section()?;
+        if let Some((_, first)) = sections.iter().find(|(n, _)| *n == name) {
+            parse_error!("duplicate section `{}`", name;
+                code = "E0042",
+                label(*first) = "first defined here",
+                note = "section names are case-sensitive",
+                help = "rename one of the sections")?;
+        }
+
+        sections.push((name, span));
+        skip_while(|c| *c == '\n')?;
+    }
+
+    sections.into_iter().map(|(name, _)| name).collect::<Vec<_>>()
+}
+
+const SOURCE: &str = "[a]\n[b]\n[a]\n";
+
+#[test]
+#[cfg(feature = "codespan-reporting")]
+fn test_codespan() {
+    use codespan_reporting::diagnostic::{Severity, LabelStyle};
+    use codespan_reporting::files::SimpleFile;
+    use codespan_reporting::term::{emit_into_string, Config};
+
+    let error = parse!(ini: Text::from(SOURCE)).unwrap_err();
+    let diagnostic = error.to_codespan(7);
+    assert_eq!(diagnostic.severity, Severity::Error);
+    assert_eq!(diagnostic.code.as_deref(), Some("E0042"));
+    assert_eq!(diagnostic.message, "duplicate section `a`");
+    assert_eq!(diagnostic.notes, ["note: section names are case-sensitive", "help: rename one of the sections"]);
+
+    let labels: Vec<_> = diagnostic.labels.iter()
+        .map(|l| (l.style, l.file_id, l.range.clone(), l.message.as_str()))
+        .collect();
+
+    assert_eq!(labels, [
+        (LabelStyle::Primary, 7, 0..11, ""),
+        (LabelStyle::Secondary, 7, 0..3, "first defined here"),
+        (LabelStyle::Secondary, 7, 0..11, "while parsing `ini`"),
+    ]);
+
+    let file = SimpleFile::new("sections.ini", SOURCE);
+    let string = emit_into_string(&Config::default(), &file, &error.to_codespan(())).unwrap();
+    assert!(string.starts_with("error[E0042]: duplicate section `a`"));
+    assert!(string.contains("first defined here"));
+
+    let owned = error.into_owned();
+    assert_eq!(owned.to_codespan(()).labels.len(), 3);
+}
+
+#[test]
+#[cfg(feature = "ariadne")]
+fn test_ariadne() {
+    use ariadne::{Config, IndexType, Source};
+
+    let source = "[ä]\n[b]\n[ä]\n";
+    let error = parse!(ini: Text::from(source)).unwrap_err();
+    let config = Config::default().with_color(false).with_index_type(IndexType::Byte);
+    let report = error.to_ariadne("sections.ini").with_config(config).finish();
+
+    let mut buffer = vec![];
+    report.write(("sections.ini", Source::from(source)), &mut buffer).unwrap();
+    let string = String::from_utf8(buffer).unwrap();
+    assert!(string.starts_with("[E0042] Error: duplicate section `ä`"), "{}", string);
+    assert!(string.contains("sections.ini:1:1"), "{}", string);
+    assert!(string.contains("first defined here"));
+    assert!(string.contains("while parsing `ini`"));
+    assert!(string.contains("Note: section names are case-sensitive"));
+    assert!(string.contains("Help: rename one of the sections"));
+}
+
+#[test]
+#[cfg(feature = "miette")]
+fn test_miette() {
+    use miette::{Diagnostic, NarratableReportHandler};
+
+    let error = parse!(ini: Text::from(SOURCE)).unwrap_err().into_owned();
+    assert_eq!(Diagnostic::code(&error).unwrap().to_string(), "E0042");
+    assert_eq!(Diagnostic::help(&error).unwrap().to_string(),
+        "rename one of the sections\nnote: section names are case-sensitive");
+
+    let labels: Vec<_> = Diagnostic::labels(&error).unwrap().collect();
+    assert_eq!(labels.len(), 3);
+    assert!(labels[0].primary());
+    assert_eq!((labels[0].offset(), labels[0].len()), (0, 11));
+    assert_eq!(labels[1].label(), Some("first defined here"));
+    assert_eq!((labels[1].offset(), labels[1].len()), (0, 3));
+
+    let report = miette::Report::new(error).with_source_code(SOURCE);
+    let mut string = String::new();
+    NarratableReportHandler::new().render_report(&mut string, report.as_ref()).unwrap();
+    assert!(string.contains("first defined here"));
+    assert!(string.contains("diagnostic code: E0042"));
+}
@@ -0,0 +1,82 @@
+#![cfg(feature = "ropey")]
+
+use std::borrow::Cow;
+
+use ropey::Rope;
+use pear::input::{Pear, RopeInput, Span};
+use pear::{macros::*, parsers::*};
+
+type Input<'a> = Pear<RopeInput<'a>>;
+type Result<'a, T> = pear::input::Result<T, RopeInput<'a>>;
+
+#[parser]
+fn assignment<'a>(input: &mut Input<'a>) -> Result<'a, (Cow<'a, str>, Cow<'a, str>)> {
+    let key = take_some_while(|c| c.is_alphanumeric())?;
+    eat_slice(" = ")?;
+    let value = take_some_while(|&c| c != '\n')?;
+    (key, value)
+}
+
+#[parser]
+fn assignments<'a>(input: &mut Input<'a>) -> Result<'a, Vec<(Cow<'a, str>, Cow<'a, str>)>> {
+    let mut pairs = Vec::new();
+    pairs.push(assignment()?);
+    while parse_try!(eat('\n')).is_some() {
+        pairs.push(assignment()?);
+    }
+
+    pairs
+}
+
+#[parser]
+fn word<'a>(input: &mut Input<'a>) -> Result<'a, Span<'a>> {
+    take_some_while(|c| c.is_alphabetic())?;
+    parse_context!()
+}
+
+#[test]
+fn test_rope_parse() {
+    let rope = Rope::from_str("a = 1\nbé = two\nc = 3");
+    let pairs = parse!(assignments: RopeInput::from(&rope)).unwrap();
+    assert_eq!(pairs, vec![
+        ("a".into(), "1".into()),
+        ("bé".into(), "two".into()),
+        ("c".into(), "3".into())
+    ]);
+}
+
+#[test]
+fn test_rope_context() {
+    let rope = Rope::from_str("a = 1\nbé = two\nc ! 3");
+    let error = parse!(assignments: RopeInput::from(&rope)).unwrap_err();
+    assert_eq!(error.info.context, Span {
+        start: (3, 2, 17),
+        end: (3, 2, 17),
+        cursor: Some(' '),
+        snippet: Some(""),
+    });
+}
+
+#[test]
+fn test_rope_across_chunks() {
+    let text = "ab".repeat(4096);
+    let mut rope = Rope::from_str(&text);
+    rope.insert(0, "x");
+
+    let mut input: Input<'_> = Pear::new(&rope);
+    eat(&mut input, 'x').unwrap();
+    match eat_slice(&mut input, &*text).unwrap() {
+        Cow::Owned(slice) => assert_eq!(slice, text.as_str()),
+        Cow::Borrowed(_) => panic!("expected slice to span chunks"),
+    }
+
+    eof(&mut input).unwrap();
+
+    // A context across chunks has no snippet rather than a truncated one.
+    let span = parse!(word: RopeInput::from(&rope)).unwrap();
+    assert_eq!((span.start, span.end), ((1, 1, 0), (1, 8194, 8193)));
+    assert_eq!(span.snippet, None);
+
+    let span = parse!(word: RopeInput::from(rope.byte_slice(1..5))).unwrap();
+    assert_eq!(span.snippet, Some("abab"));
+}
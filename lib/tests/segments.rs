@@ -0,0 +1,66 @@
+use std::borrow::Cow;
+use std::collections::VecDeque;
+
+use pear::input::{Pear, Segments, Extent};
+use pear::error::Expected;
+use pear::{macros::*, parsers::*, combinators::*};
+
+type Result<'a, T> = pear::input::Result<T, Segments<'a>>;
+type Bytes<'a> = Extent<Cow<'a, [u8]>>;
+
+#[parser]
+fn header<'a>(input: &mut Pear<Segments<'a>>) -> Result<'a, (Bytes<'a>, Bytes<'a>)> {
+    let name = take_some_while(|&b| b != b':')?;
+    eat_slice(&b": "[..])?;
+    let value = take_while(|&b| b != b'\r')?;
+    eat_slice(&b"\r\n"[..])?;
+    (name, value)
+}
+
+#[parser]
+fn headers<'a>(input: &mut Pear<Segments<'a>>) -> Result<'a, Vec<(Bytes<'a>, Bytes<'a>)>> {
+    let headers: Vec<_> = try_collect(header)?;
+    eat_slice(&b"\r\n"[..])?;
+    headers
+}
+
+#[test]
+fn test_segments_across_boundaries() {
+    let mut chunks: VecDeque<Vec<u8>> = VecDeque::new();
+    chunks.push_back(b"Host: exa".to_vec());
+    chunks.push_back(b"mple.com\r".to_vec());
+    chunks.push_back(vec![]);
+    chunks.push_back(b"\nAccept: */*\r\n\r\n".to_vec());
+
+    let headers = parse!(headers: Segments::from(&chunks)).unwrap();
+    assert_eq!(headers.len(), 2);
+
+    let (name, value) = &headers[0];
+    assert!(matches!(name.values, Cow::Borrowed(b"Host")));
+    assert!(matches!(value.values, Cow::Owned(_)));
+    assert_eq!(*value, &b"example.com"[..]);
+    assert_eq!((value.start, value.end), (6, 17));
+
+    let (name, value) = &headers[1];
+    assert!(matches!(name.values, Cow::Borrowed(b"Accept")));
+    assert!(matches!(value.values, Cow::Borrowed(b"*/*")));
+}
+
+#[test]
+fn test_segments_rewind() {
+    let chunks: &[&[u8]] = &[b"ab", b"c", b"de"];
+    let mut input: Pear<Segments<'_>> = Pear::new(Segments::new(chunks));
+
+    let result = take_while_window(&mut input, 2, |s| *s != &b"de"[..]).unwrap();
+    assert_eq!(result, &b"abc"[..]);
+    assert_eq!(eat_slice(&mut input, &b"de"[..]).unwrap(), &b"de"[..]);
+    eof(&mut input).unwrap();
+}
+
+#[test]
+fn test_segments_error_context() {
+    let chunks: &[&[u8]] = &[b"Host", b": x\r", b"!"];
+    let error = parse!(header: Segments::new(chunks)).unwrap_err();
+    assert_eq!((error.info.context.start, error.info.context.end), (7, 7));
+    assert!(matches!(error.error, Expected::Slice(_, Some(ref found)) if *found == &b"\r!"[..]));
+}
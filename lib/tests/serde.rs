@@ -0,0 +1,113 @@
+#![cfg(feature = "serde")]
+
+use pear::input::{Pear, Text, Cursor, Extent, Span, OwnedSpan, Limit, IntoOwned};
+use pear::error::{Expected, ParseError};
+use pear::{macros::*, parsers::*};
+
+use serde_json::json;
+
+type Input<'a> = Pear<Text<'a>>;
+type Result<'a, T> = pear::input::Result<T, Text<'a>>;
+
+#[parser]
+fn string<'a>(input: &mut Input<'a>) -> Result<'a, &'a str> {
+    eat('"')?;
+    let string = take_while(|c| *c != '"')?;
+    eat('"')?;
+    string
+}
+
+#[parser]
+fn object<'a>(input: &mut Input<'a>) -> Result<'a, (&'a str, &'a str)> {
+    eat('{')?;
+    let key = string()?;
+    eat(':')?;
+    let value = string()?;
+    eat('}')?;
+    (key, value)
+}
+
+#[test]
+fn test_serialize_error() {
+    let error = parse!(object: Text::from(r#"{"a":x}"#)).unwrap_err();
+    let value = serde_json::to_value(&error).unwrap();
+    let here = json!({ "start": [1, 6, 5], "end": [1, 6, 5], "cursor": "x", "snippet": "" });
+    assert_eq!(value, json!({
+        "error": { "kind": "token", "expected": "'\"'", "found": "x" },
+        "info": { "parser": { "name": "eat", "raw": true }, "context": here },
+        "stack": [
+            { "parser": { "name": "eat", "raw": true }, "context": here },
+            { "parser": { "name": "string", "raw": false }, "context": here },
+            {
+                "parser": { "name": "object", "raw": false },
+                "context": { "start": [1, 1, 0], "end": [1, 6, 5], "cursor": "x", "snippet": "{\"a\":" }
+            }
+        ]
+    }));
+}
+
+#[test]
+fn test_round_trip() {
+    let error = parse!(object: Text::from(r#"{"a":x}"#)).unwrap_err()
+        .with_code("E0001")
+        .with_help("quote the value");
+
+    let json = serde_json::to_string(&error).unwrap();
+    let back: ParseError<OwnedSpan, Expected<char, String>> = serde_json::from_str(&json).unwrap();
+    assert_eq!(back.code(), Some("E0001"));
+    assert_eq!(back.help(), Some("quote the value"));
+    assert_eq!(back.info.parser.name, "eat");
+    assert_eq!(back.stack[2].parser.name, "object");
+    assert_eq!(back.info.context, error.info.context.into_owned());
+    assert_eq!(back.to_string(), error.into_owned().to_string());
+
+    // A span without escapes can be deserialized borrowing its snippet.
+    let span = Span { start: (1, 1, 0), end: (1, 3, 2), cursor: None, snippet: Some("ab") };
+    let json = serde_json::to_string(&span).unwrap();
+    assert_eq!(serde_json::from_str::<Span<'_>>(&json).unwrap(), span);
+}
+
+#[test]
+fn test_expected_kinds() {
+    let expected: Vec<Expected<char, String>> = vec![
+        Expected::Eof(Some('a')),
+        Expected::Incomplete { needed: Some(2) },
+        Expected::Malformed { encoding: "utf-8", offset: 3 },
+        Expected::TooDeep { limit: 10 },
+        Expected::LimitExceeded(Limit::InputLength(5)),
+        Expected::Foreign(std::sync::Arc::new("1.x".parse::<f64>().unwrap_err())),
+        Expected::from("oops"),
+        Expected::Elided,
+    ];
+
+    let json = serde_json::to_value(&expected).unwrap();
+    assert_eq!(json, json!([
+        { "kind": "eof", "found": "a" },
+        { "kind": "incomplete", "needed": 2 },
+        { "kind": "malformed", "encoding": "utf-8", "offset": 3 },
+        { "kind": "too_deep", "limit": 10 },
+        { "kind": "limit_exceeded", "limit": { "input_length": 5 } },
+        { "kind": "foreign", "message": "invalid float literal" },
+        { "kind": "other", "message": "oops" },
+        { "kind": "elided" },
+    ]));
+
+    let back: Vec<Expected<char, String>> = serde_json::from_value(json).unwrap();
+    for (a, b) in expected.iter().zip(back.iter()) {
+        assert_eq!(a.to_string(), b.to_string());
+    }
+}
+
+#[test]
+fn test_extent() {
+    let mut input = Pear::<Cursor<&[u8]>>::new(&b"abc"[..]);
+    let error = eat_slice(&mut input, &b"abd"[..]).unwrap_err().unwrap();
+    let value = serde_json::to_value(error.info.context).unwrap();
+    assert_eq!(value, json!({ "start": 0, "end": 0, "values": [] }));
+
+    let extent: Extent<Vec<u8>> = serde_json::from_value(json!({
+        "start": 1, "end": 3, "values": [98, 99]
+    })).unwrap();
+
+    assert_eq!(extent, Extent { start: 1, end: 3, values: b"bc".to_vec() });
+}
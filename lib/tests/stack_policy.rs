@@ -0,0 +1,101 @@
+use pear::input::{Pear, Text, StackPolicy};
+use pear::{macros::*, parsers::*};
+
+type Input<'a> = Pear<Text<'a>>;
+type Result<'a, T> = pear::input::Result<T, Text<'a>>;
+
+#[parser]
+fn value<'a>(input: &mut Input<'a>) -> Result<'a, usize> {
+    switch! {
+        eat('[') => {
+            let depth = value()?;
+            eat(']')?;
+            depth + 1
+        },
+        _ => {
+            eat('0')?;
+            0
+        }
+    }
+}
+
+#[parser(label = "document")]
+fn root<'a>(input: &mut Input<'a>) -> Result<'a, usize> {
+    let depth = value()?;
+    eof()?;
+    depth
+}
+
+#[parser(hidden)]
+fn hidden_root<'a>(input: &mut Input<'a>) -> Result<'a, usize> {
+    value()?
+}
+
+fn names(mut input: Input<'_>) -> Vec<(&'static str, usize)> {
+    let error = root(&mut input).unwrap_err().unwrap();
+    error.stack.iter().map(|info| (info.parser.name, info.count)).collect()
+}
+
+#[test]
+fn test_default_policy() {
+    let stack = names(Input::new("[[[1]]]"));
+    assert_eq!(stack, [("eat", 1), ("value", 1), ("value", 1), ("value", 1),
+        ("value", 1), ("document", 1)]);
+
+    assert_eq!(Input::new("").options.stack_policy, StackPolicy::default());
+}
+
+#[test]
+fn test_hide_raw() {
+    let policy = StackPolicy::default().hide_raw(true);
+    let stack = names(Input::new("[[[1]]]").with_stack_policy(policy));
+    assert_eq!(stack, [("value", 1), ("value", 1), ("value", 1), ("value", 1), ("document", 1)]);
+}
+
+#[test]
+fn test_collapse() {
+    let policy = StackPolicy::default().collapse(true);
+    let stack = names(Input::new("[[[1]]]").with_stack_policy(policy));
+    assert_eq!(stack, [("eat", 1), ("value", 4), ("document", 1)]);
+
+    let mut input = Input::new("[[[1]]]").with_stack_policy(StackPolicy::concise());
+    let error = root(&mut input).unwrap_err().unwrap();
+    let lines: Vec<_> = error.to_string().lines().map(String::from).collect();
+    assert_eq!(lines.len(), 3);
+    assert!(lines[1].starts_with(" + value ×4 1:4"), "{}", lines[1]);
+    assert!(lines[2].starts_with(" + document 1:1"), "{}", lines[2]);
+
+    // Entries of the same parser that aren't consecutive aren't collapsed.
+    let stack = names(Input::new("[0]x").with_stack_policy(StackPolicy::concise()));
+    assert_eq!(stack, [("document", 1)]);
+}
+
+#[test]
+fn test_max_frames() {
+    let policy = StackPolicy::concise().max_frames(1);
+    let stack = names(Input::new("[[[1]]]").with_stack_policy(policy));
+    assert_eq!(stack, [("value", 4)]);
+
+    let policy = StackPolicy::default().max_frames(3);
+    let stack = names(Input::new("[[[1]]]").with_stack_policy(policy));
+    assert_eq!(stack, [("eat", 1), ("value", 1), ("value", 1)]);
+
+    let policy = StackPolicy::default().max_frames(0);
+    assert!(names(Input::new("[[[1]]]").with_stack_policy(policy)).is_empty());
+}
+
+#[test]
+fn test_hidden() {
+    let mut input = Input::new("[[1]]").with_stack_policy(StackPolicy::concise());
+    let error = hidden_root(&mut input).unwrap_err().unwrap();
+    let stack: Vec<_> = error.stack.iter().map(|info| (info.parser.name, info.count)).collect();
+    assert_eq!(stack, [("value", 3)]);
+}
+
+#[test]
+fn test_label() {
+    let error = parse!(root: Text::from("[0")).unwrap_err();
+    assert_eq!(error.info.parser.name, "eat");
+    assert_eq!(error.stack.last().unwrap().parser.name, "document");
+    assert!(error.to_string().contains(" + document 1:1"));
+}
@@ -0,0 +1,167 @@
+use std::collections::HashSet;
+
+use pear::input::{Pear, Text, Input as _};
+use pear::{macros::*, parsers::*, combinators::*};
+
+type Input<'a> = Pear<Text<'a>, HashSet<&'a str>>;
+type Result<'a, T> = pear::input::Result<T, Text<'a>>;
+
+#[derive(Debug, PartialEq)]
+enum Statement<'a> {
+    Typedef(&'a str),
+    Declaration(&'a str, &'a str),
+    Product(&'a str, &'a str),
+}
+
+#[parser]
+fn ident<'a>(input: &mut Input<'a>) -> Result<'a, &'a str> {
+    let ident = take_some_while(|c| c.is_ascii_alphanumeric())?;
+    skip_while(|c| c.is_whitespace())?;
+    ident
+}
+
+#[parser]
+fn punct<'a>(input: &mut Input<'a>, c: char) -> Result<'a, ()> {
+    eat(c)?;
+    skip_while(|c| c.is_whitespace())?;
+}
+
+#[parser(rewind)]
+fn typedef<'a>(input: &mut Input<'a>) -> Result<'a, Statement<'a>> {
+    eat_slice("typedef ")?;
+    let name = ident()?;
+    input.state_mut().insert(name);
+    peek(';')?;
+    Statement::Typedef(name)
+}
+
+#[parser(rewind)]
+fn type_name<'a>(input: &mut Input<'a>) -> Result<'a, &'a str> {
+    let name = ident()?;
+    if !input.state().contains(name) {
+        parse_error!("a type name")?;
+    }
+
+    name
+}
+
+#[parser]
+fn statement<'a>(input: &mut Input<'a>) -> Result<'a, Statement<'a>> {
+    let statement = switch! {
+        s@typedef() => s,
+        ty@type_name() => {
+            punct('*')?;
+            Statement::Declaration(ty, ident()?)
+        },
+        _ => {
+            let left = ident()?;
+            punct('*')?;
+            Statement::Product(left, ident()?)
+        }
+    };
+
+    punct(';')?;
+    statement
+}
+
+#[parser]
+fn statements<'a>(input: &mut Input<'a>) -> Result<'a, Vec<Statement<'a>>> {
+    let statements: Vec<_> = try_collect(statement)?;
+    statements
+}
+
+#[test]
+fn test_typedef_ambiguity() {
+    let mut input = Pear::with_state(Text::from("typedef T; T * x; a * b;"), HashSet::new());
+    let statements = parse!(statements: &mut input).unwrap();
+    assert_eq!(statements, vec![
+        Statement::Typedef("T"),
+        Statement::Declaration("T", "x"),
+        Statement::Product("a", "b"),
+    ]);
+
+    assert_eq!(input.into_state(), ["T"].iter().copied().collect());
+}
+
+#[test]
+fn test_rewind_restores_state() {
+    // The typedef is missing its `;`, so the insertion is undone.
+    let mut input = Pear::with_state(Text::from("typedef T x;"), HashSet::new());
+    assert!(typedef(&mut input).is_err());
+    assert!(input.state().is_empty());
+    assert_eq!(input.token(), Some('t'));
+
+    let mut input = Pear::with_state(Text::from("typedef T;"), HashSet::new());
+    assert_eq!(statement(&mut input).unwrap(), Statement::Typedef("T"));
+    assert!(input.state().contains("T"));
+}
+
+#[parser]
+fn declare<'a>(input: &mut Input<'a>) -> Result<'a, ()> {
+    let name = ident()?;
+    input.state_mut().insert(name);
+    punct(';')?;
+}
+
+#[test]
+fn test_try_collect_restores_state() {
+    let mut input = Pear::with_state(Text::from("A; B; C"), HashSet::new());
+    let declared: Vec<_> = try_collect(&mut input, declare).unwrap();
+    assert_eq!(declared.len(), 2);
+
+    let mut names: Vec<_> = input.state().iter().copied().collect();
+    names.sort();
+    assert_eq!(names, ["A", "B"]);
+}
+
+#[test]
+fn test_switch_restores_state() {
+    let mut input = Pear::with_state(Text::from("A."), HashSet::new());
+    let declared = switch_declare(&mut input).unwrap();
+    assert!(!declared);
+    assert!(input.state().is_empty());
+}
+
+#[parser]
+fn switch_declare<'a>(input: &mut Input<'a>) -> Result<'a, bool> {
+    switch! {
+        declare() => true,
+        _ => false
+    }
+}
+
+#[parser]
+fn nested<'a>(input: &mut Pear<Text<'a>, usize>) -> pear::input::Result<usize, Text<'a>> {
+    eat('(')?;
+    *input.state_mut() += 1;
+    let depth = *input.state();
+    let inner = switch! {
+        peek('(') => nested()?,
+        _ => depth
+    };
+
+    eat(')')?;
+    *input.state_mut() -= 1;
+    inner
+}
+
+#[test]
+fn test_checkpoints() {
+    let mut input = Pear::with_state(Text::from("((()))"), 0);
+    assert_eq!(nested(&mut input).unwrap(), 3);
+    assert_eq!(*input.state(), 0);
+
+    let mut input: Pear<Text<'_>, usize> = Pear::with_state("", 0);
+    let outer = input.checkpoint_state();
+    *input.state_mut() = 1;
+    let inner = input.checkpoint_state();
+    *input.state_mut() = 2;
+    input.restore_state(inner);
+    assert_eq!(*input.state(), 1);
+    input.release_state(inner);
+
+    *input.state_mut() = 3;
+    input.restore_state(outer);
+    assert_eq!(*input.state(), 0);
+    input.release_state(outer);
+}
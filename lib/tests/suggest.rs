@@ -0,0 +1,120 @@
+use pear::input::{Pear, Text, Cursor};
+use pear::{macros::*, parsers::*, combinators::*};
+
+type Input<'a> = Pear<Text<'a>>;
+type Result<'a, T> = pear::input::Result<T, Text<'a>>;
+
+#[derive(Debug, PartialEq)]
+enum Value {
+    Bool(bool),
+    Null,
+    Array(Vec<Value>),
+}
+
+#[parser]
+fn value<'a>(input: &mut Input<'a>) -> Result<'a, Value> {
+    switch! {
+        eat_slice("true") => Value::Bool(true),
+        eat_slice("false") => Value::Bool(false),
+        eat_slice("null") => Value::Null,
+        eat('[') => {
+            let values = series(value, ',')?;
+            eat(']')?;
+            Value::Array(values)
+        },
+        _ => parse_error!("expected a value")?
+    }
+}
+
+#[parser(rewind)]
+fn keyword<'a>(input: &mut Input<'a>) -> Result<'a, &'a str> {
+    eat('@')?;
+    switch! {
+        eat_slice("import") => "import",
+        _ => eat_slice("include")?
+    }
+}
+
+#[parser]
+fn greeting<'a>(input: &mut Input<'a>) -> Result<'a, &'a str> {
+    eat_slice("こんにちは")?
+}
+
+#[allow(clippy::result_large_err)]
+fn parse(source: &str) -> Result<'_, Value> {
+    let mut input = Input::new(source).with_suggestions();
+    value(&mut input)
+}
+
+#[test]
+fn test_suggestion() {
+    assert_eq!(parse("[true,null]").unwrap(), Value::Array(vec![Value::Bool(true), Value::Null]));
+
+    let error = parse("fasle").unwrap_err().unwrap();
+    assert_eq!(error.help(), Some("did you mean `false`?"));
+    assert_eq!(error.suggestions().len(), 1);
+
+    let suggestion = &error.suggestions()[0];
+    assert_eq!(suggestion.replacement, "false");
+    assert_eq!((suggestion.context.start, suggestion.context.end), ((1, 1, 0), (1, 6, 5)));
+    assert!(error.to_string().ends_with("\n = help: did you mean `false`?"));
+}
+
+#[test]
+fn test_suggestion_lengths() {
+    let error = parse("[true,nul]").unwrap_err().unwrap();
+    assert_eq!(error.help(), Some("did you mean `null`?"));
+    assert_eq!(error.suggestions()[0].context.start, (1, 7, 6));
+    assert_eq!(error.suggestions()[0].context.end, (1, 10, 9));
+
+    let error = parse("[ture").unwrap_err().unwrap();
+    assert_eq!(error.suggestions()[0].replacement, "true");
+    assert_eq!(error.suggestions()[0].context.snippet, Some("ture"));
+
+    let error = parse("fxlsxy").unwrap_err().unwrap();
+    assert_eq!(error.suggestions().len(), 0);
+}
+
+#[test]
+fn test_multibyte_suggestion() {
+    let mut input = Input::new("こんちは").with_suggestions();
+    let error = greeting(&mut input).unwrap_err().unwrap();
+    assert_eq!(error.suggestions()[0].replacement, "こんにちは");
+    assert_eq!(error.suggestions()[0].context.snippet, Some("こんちは"));
+}
+
+#[test]
+fn test_raw_parsers_dont_suggest() {
+    let mut input = Input::new("fasle").with_suggestions();
+    assert!(eat_slice(&mut input, "false").unwrap_err().unwrap().suggestions().is_empty());
+}
+
+#[test]
+fn test_no_suggestion() {
+    let error = parse("xyz").unwrap_err().unwrap();
+    assert!(error.suggestions().is_empty());
+    assert_eq!(error.help(), None);
+
+    // Suggestions are opt-in.
+    let error = value(&mut Input::new("fasle")).unwrap_err().unwrap();
+    assert!(error.suggestions().is_empty());
+}
+
+#[test]
+fn test_rewound_suggestion() {
+    let mut input = Input::new("@improt").with_suggestions();
+    let error = keyword(&mut input).unwrap_err().unwrap();
+    assert_eq!(error.suggestions()[0].replacement, "import");
+    assert_eq!(error.suggestions()[0].context.snippet, Some("improt"));
+
+    let mut input = Input::new("@inclde").with_suggestions();
+    let error = keyword(&mut input).unwrap_err().unwrap();
+    assert_eq!(error.suggestions()[0].replacement, "include");
+}
+
+#[test]
+fn test_non_text_input() {
+    let mut input = Pear::<Cursor<&[u8]>>::new(&b"fasle"[..]).with_suggestions();
+    let result = eat_slice(&mut input, &b"false"[..]);
+    assert!(result.unwrap_err().unwrap().suggestions().is_empty());
+}
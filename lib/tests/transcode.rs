@@ -0,0 +1,116 @@
+use pear::input::{Pear, Transcoded, Encoding, OwnedSpan};
+use pear::error::Expected;
+use pear::{macros::*, parsers::*, combinators::*};
+
+type Input<'a> = Pear<Transcoded<'a>>;
+type Result<'a, T> = pear::input::Result<T, Transcoded<'a>>;
+
+#[parser]
+fn assignment<'a>(input: &mut Input<'a>) -> Result<'a, (String, String)> {
+    let key = take_some_while(|c| c.is_alphanumeric())?;
+    eat_slice(" = ")?;
+    let value = take_some_while(|&c| c != '\n')?;
+    eat('\n')?;
+    (key, value)
+}
+
+#[parser]
+fn assignments<'a>(input: &mut Input<'a>) -> Result<'a, Vec<(String, String)>> {
+    let pairs: Vec<_> = try_collect(assignment)?;
+    eof()?;
+    pairs
+}
+
+fn utf16(string: &str, bom: bool, to_bytes: fn(u16) -> [u8; 2]) -> Vec<u8> {
+    let bom = if bom { Some(0xFEFF) } else { None };
+    bom.into_iter().chain(string.encode_utf16()).flat_map(to_bytes).collect()
+}
+
+fn pairs(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+    pairs.iter().map(|&(k, v)| (k.into(), v.into())).collect()
+}
+
+#[test]
+fn test_utf16() {
+    let text = "a = 1\nbé = 𝄞\n";
+    let expected = pairs(&[("a", "1"), ("bé", "𝄞")]);
+
+    let le = utf16(text, false, u16::to_le_bytes);
+    assert_eq!(parse!(assignments: Transcoded::utf16(&le)).unwrap(), expected);
+
+    let le = utf16(text, true, u16::to_le_bytes);
+    assert_eq!(parse!(assignments: Transcoded::utf16(&le)).unwrap(), expected);
+
+    let be = utf16(text, true, u16::to_be_bytes);
+    assert_eq!(parse!(assignments: Transcoded::utf16(&be)).unwrap(), expected);
+
+    let be = utf16(text, false, u16::to_be_bytes);
+    let input = Transcoded::new(&be, Encoding::Utf16Be);
+    assert_eq!(parse!(assignments: input).unwrap(), expected);
+}
+
+#[test]
+fn test_utf16_context() {
+    let bytes = utf16("a = 1\nbé ! 2\n", true, u16::to_be_bytes);
+    let mut input: Input<'_> = Pear::new(Transcoded::utf16(&bytes));
+    assignment(&mut input).unwrap();
+
+    let error = assignment(&mut input).unwrap_err().unwrap();
+    assert_eq!(error.info.context, OwnedSpan {
+        start: (2, 4, 18),
+        end: (2, 4, 18),
+        cursor: Some(' '),
+        snippet: Some("".into()),
+    });
+}
+
+#[test]
+fn test_utf16_positions() {
+    use pear::input::{Input as _, Rewind, ParserInfo};
+
+    let bytes = utf16("ab\ncé\nd", false, u16::to_le_bytes);
+    let mut input = Transcoded::utf16(&bytes);
+    let info = ParserInfo { name: "test", raw: false };
+    assert!(input.has(8) && !input.has(9));
+
+    input.take(|_| true);
+    let end = input.context(0);
+    assert_eq!((end.start, end.end), ((1, 1, 0), (3, 2, 14)));
+
+    // Positions before the last one computed are decoded from the start.
+    input.rewind_to(6);
+    let mark = input.mark(&info);
+    input.take(|&c| c != '\n');
+    let span = input.context(mark);
+    assert_eq!((span.start, span.end), ((2, 1, 6), (2, 4, 10)));
+    assert_eq!(span.snippet.as_deref(), Some("cé"));
+    assert!(input.has(2) && !input.has(3));
+}
+
+#[test]
+fn test_utf16_malformed() {
+    let mut bytes = utf16("a = ", false, u16::to_le_bytes);
+    bytes.extend_from_slice(&0xD800u16.to_le_bytes());
+    bytes.extend_from_slice(&utf16("x\n", false, u16::to_le_bytes));
+
+    let mut input: Input<'_> = Pear::new(Transcoded::utf16(&bytes));
+    let error = assignment(&mut input).unwrap_err().unwrap();
+    assert!(matches!(error.error, Expected::Malformed { encoding: "UTF-16LE", offset: 8 }));
+}
+
+#[test]
+fn test_single_byte() {
+    let bytes = b"caf\xe9 = \xa31\n";
+    let expected = pairs(&[("café", "£1")]);
+    assert_eq!(parse!(assignments: Transcoded::latin1(bytes)).unwrap(), expected);
+
+    static HIGH: [char; 128] = {
+        let mut high = ['\u{FFFD}'; 128];
+        high[0x80 - 0x80] = '€';
+        high
+    };
+
+    let encoding = Encoding::SingleByte { name: "windows-1252", high: &HIGH };
+    let input = Transcoded::new(b"x = \x80\n", encoding);
+    assert_eq!(parse!(assignments: input).unwrap(), pairs(&[("x", "€")]));
+}
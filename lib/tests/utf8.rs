@@ -0,0 +1,71 @@
+use pear::input::{Pear, Partial, Utf8Bytes, Span, Stream, Status};
+use pear::error::Expected;
+use pear::{macros::*, parsers::*, combinators::*};
+
+type Input<'a> = Pear<Utf8Bytes<'a>>;
+type Result<'a, T> = pear::input::Result<T, Utf8Bytes<'a>>;
+
+#[parser]
+fn assignment<'a>(input: &mut Input<'a>) -> Result<'a, (&'a str, &'a str)> {
+    let key = take_some_while(|c| c.is_alphanumeric())?;
+    eat_slice(" = ")?;
+    let value = take_some_while(|&c| c != '\n')?;
+    eat('\n')?;
+    (key, value)
+}
+
+#[parser]
+fn assignments<'a>(input: &mut Input<'a>) -> Result<'a, Vec<(&'a str, &'a str)>> {
+    let pairs: Vec<_> = try_collect(assignment)?;
+    eof()?;
+    pairs
+}
+
+#[parser]
+fn word<'a>(input: &mut Pear<Partial<Utf8Bytes<'a>>>) -> pear::input::Result<&'a str, Partial<Utf8Bytes<'a>>> {
+    let word = take_some_while(|c| c.is_alphabetic())?;
+    eat('\n')?;
+    word
+}
+
+#[test]
+fn test_utf8_parse() {
+    let bytes = "a = 1\nbé = twö\n".as_bytes();
+    let pairs = parse!(assignments: Utf8Bytes::from(bytes)).unwrap();
+    assert_eq!(pairs, vec![("a", "1"), ("bé", "twö")]);
+}
+
+#[test]
+fn test_utf8_malformed() {
+    let bytes = &b"a = 1\nb = t\xc3\n"[..];
+    let mut input: Input<'_> = Pear::new(bytes);
+    assert_eq!(assignment(&mut input).unwrap(), ("a", "1"));
+    let error = assignment(&mut input).unwrap_err().unwrap();
+    assert!(matches!(error.error, Expected::Malformed { encoding: "UTF-8", offset: 11 }));
+    assert_eq!(error.error.to_string(), "invalid UTF-8 at byte offset 11");
+    assert_eq!(error.info.context, Span {
+        start: (2, 6, 11),
+        end: (2, 6, 11),
+        cursor: None,
+        snippet: Some(""),
+    });
+}
+
+#[test]
+fn test_utf8_stream() {
+    let mut stream = Stream::new();
+    stream.feed(b"gr\xc3");
+    assert!(matches!(stream.parse(word), Status::Incomplete(_)));
+
+    stream.feed(b"\xbcn\n");
+    match stream.parse(word) {
+        Status::Complete(word) => assert_eq!(word, "grün"),
+        _ => panic!("expected a complete word"),
+    }
+
+    stream.feed(b"gr\xff\n");
+    match stream.parse(word) {
+        Status::Error(e) => assert!(matches!(e.error, Expected::Malformed { offset: 2, .. })),
+        _ => panic!("expected malformed input"),
+    }
+}